@@ -83,8 +83,9 @@ pub fn home_dir() -> Option<PathBuf> {
     var("HOME").ok().map(PathBuf::from)
 }
 
+/// Method to return the directory for temporary files, from `TMPDIR` if it is set
 pub fn temp_dir() -> Option<PathBuf> {
-    Some(PathBuf::from("/tmp"))
+    Some(var("TMPDIR").ok().map(PathBuf::from).unwrap_or(PathBuf::from("/tmp")))
 }
 
 /// Set the current directory
@@ -0,0 +1,141 @@
+use io::{Error, ErrorKind, Result};
+use os::unix::fs::{symlink, DirBuilderExt, PermissionsExt};
+use path::{Path, PathBuf};
+use vec::Vec;
+
+use super::{canonicalize, copy, read_link, read_dir, remove_file, set_permissions, symlink_metadata, DirBuilder};
+
+/// Options for `copy_dir_all`
+#[derive(Clone, Debug)]
+pub struct CopyOptions {
+    overwrite: bool,
+    skip_existing: bool,
+    follow_links: bool,
+}
+
+impl CopyOptions {
+    /// Create options that fail on existing files and copy links as links
+    pub fn new() -> CopyOptions {
+        CopyOptions {
+            overwrite: false,
+            skip_existing: false,
+            follow_links: false,
+        }
+    }
+
+    /// Replace files that already exist in the destination
+    pub fn overwrite(&mut self, overwrite: bool) -> &mut CopyOptions {
+        self.overwrite = overwrite;
+        self
+    }
+
+    /// Leave files that already exist in the destination untouched, instead of failing
+    pub fn skip_existing(&mut self, skip_existing: bool) -> &mut CopyOptions {
+        self.skip_existing = skip_existing;
+        self
+    }
+
+    /// Copy the targets of symbolic links, instead of recreating the links
+    pub fn follow_links(&mut self, follow_links: bool) -> &mut CopyOptions {
+        self.follow_links = follow_links;
+        self
+    }
+}
+
+/// Recursively copy the directory at `from` to `to`, returning the number of bytes copied
+///
+/// Missing directories are given the permissions of their source once their contents are copied,
+/// so a read-only source directory can still be filled. Files are copied with `fs::copy`. If both
+/// `overwrite` and `skip_existing` are set, existing files are skipped.
+///
+/// Copying a directory into itself is an error, as is a loop of followed symbolic links.
+pub fn copy_dir_all<P: AsRef<Path>, Q: AsRef<Path>>(from: P, to: Q, options: &CopyOptions) -> Result<u64> {
+    let from = from.as_ref();
+    let to = to.as_ref();
+
+    let from_canonical = try!(canonicalize(from));
+    if try!(canonicalize_new(to)).starts_with(&from_canonical) {
+        return Err(Error::new(ErrorKind::InvalidInput, "cannot copy a directory into itself"));
+    }
+
+    let mut ancestors = vec![from_canonical];
+    copy_dir_inner(from, to, options, &mut ancestors)
+}
+
+/// Find the canonical path of a file that may not exist yet, through its nearest existing ancestor
+fn canonicalize_new(path: &Path) -> Result<PathBuf> {
+    match canonicalize(path) {
+        Err(ref err) if err.kind() == ErrorKind::NotFound => match (path.parent(), path.file_name()) {
+            (Some(parent), Some(name)) => {
+                let parent = if parent.as_os_str().is_empty() { Path::new(".") } else { parent };
+                Ok(try!(canonicalize_new(parent)).join(name))
+            },
+            _ => canonicalize(path)
+        },
+        res => res
+    }
+}
+
+/// Copy a directory, given the canonical paths of the directories being copied above it
+fn copy_dir_inner(from: &Path, to: &Path, options: &CopyOptions, ancestors: &mut Vec<PathBuf>) -> Result<u64> {
+    let metadata = try!(from.metadata());
+    if ! metadata.is_dir() {
+        return Err(Error::new(ErrorKind::InvalidInput, "source is not a directory"));
+    }
+    // The owner needs to be able to write into the directory while it is being filled
+    let mode = metadata.permissions().mode();
+    let created = ! to.is_dir();
+    try!(DirBuilder::new().recursive(true).mode(mode | 0o700).create(to));
+
+    let mut count = 0;
+    for entry in try!(read_dir(from)) {
+        let entry = try!(entry);
+        let src = entry.path();
+        let dst = to.join(entry.file_name());
+
+        let mut file_type = try!(entry.file_type());
+        if file_type.is_symlink() {
+            if options.follow_links {
+                file_type = try!(src.metadata()).file_type();
+            } else {
+                if symlink_metadata(&dst).is_ok() {
+                    if options.skip_existing {
+                        continue;
+                    } else if ! options.overwrite {
+                        return Err(Error::new(ErrorKind::AlreadyExists, "destination already exists"));
+                    }
+                    try!(remove_file(&dst));
+                }
+                try!(symlink(try!(read_link(&src)), &dst));
+                continue;
+            }
+        }
+
+        if file_type.is_dir() {
+            // Only a followed link can lead back to a directory being copied
+            let canonical = try!(canonicalize(&src));
+            if ancestors.contains(&canonical) {
+                return Err(Error::new(ErrorKind::Other, "filesystem loop found"));
+            }
+            ancestors.push(canonical);
+            let res = copy_dir_inner(&src, &dst, options, ancestors);
+            ancestors.pop();
+            count += try!(res);
+        } else {
+            if dst.exists() {
+                if options.skip_existing {
+                    continue;
+                } else if ! options.overwrite {
+                    return Err(Error::new(ErrorKind::AlreadyExists, "destination already exists"));
+                }
+            }
+            count += try!(copy(&src, &dst));
+        }
+    }
+
+    if created && mode & 0o700 != 0o700 {
+        try!(set_permissions(to, metadata.permissions()));
+    }
+
+    Ok(count)
+}
@@ -0,0 +1,93 @@
+use io::{Error, ErrorKind, Result};
+use path::{PathBuf, Pattern};
+use usize;
+use vec::Vec;
+
+use super::{metadata, walk_dir, WalkDir};
+
+/// Return an iterator over the paths matching a glob pattern
+///
+/// See `path::Pattern` for the syntax. Only the directories given by `Pattern::bases` are read,
+/// each as many levels down as the pattern can match, so alternatives like `{/usr,/etc}/*` are
+/// found under their own roots. Symbolic links are followed, and the entries of each directory
+/// are yielded sorted by name. A relative pattern is expanded in the current directory, and
+/// yields relative paths.
+///
+/// An invalid pattern is reported as an `InvalidInput` error. Errors reading a directory are
+/// yielded in place of its entries.
+pub fn glob(pattern: &str) -> Result<Glob> {
+    let compiled = try!(Pattern::new(pattern).map_err(|err| Error::new(ErrorKind::InvalidInput, err)));
+
+    let mut walks = Vec::new();
+    for (base, depth) in compiled.bases() {
+        let strip_cur_dir = base.as_os_str().is_empty();
+        let start = if strip_cur_dir {
+            PathBuf::from(".")
+        } else {
+            base
+        };
+
+        if metadata(&start).is_ok() {
+            let walk = walk_dir(&start)
+                .max_depth(depth.unwrap_or(usize::MAX))
+                .follow_links(true)
+                .sort(true);
+            walks.push((walk, strip_cur_dir));
+        }
+    }
+    // The walks are taken from the end
+    walks.reverse();
+
+    Ok(Glob {
+        pattern: compiled,
+        walks: walks,
+    })
+}
+
+/// An iterator over the paths matching a glob pattern, created by `glob`
+pub struct Glob {
+    pattern: Pattern,
+    /// The walks of the bases that exist and have not been finished, the next one last, each
+    /// with whether it was started at `.` for a relative pattern
+    walks: Vec<(WalkDir, bool)>,
+}
+
+impl Iterator for Glob {
+    type Item = Result<PathBuf>;
+
+    fn next(&mut self) -> Option<Result<PathBuf>> {
+        loop {
+            let found = match self.walks.last_mut() {
+                Some(&mut (ref mut walk, strip_cur_dir)) => next_match(&self.pattern, walk, strip_cur_dir),
+                None => return None
+            };
+            match found {
+                Some(res) => return Some(res),
+                None => {
+                    self.walks.pop();
+                }
+            }
+        }
+    }
+}
+
+/// Find the next entry of `walk` matching `pattern`, or an error reading it
+fn next_match(pattern: &Pattern, walk: &mut WalkDir, strip_cur_dir: bool) -> Option<Result<PathBuf>> {
+    for entry in walk {
+        match entry {
+            Ok(entry) => {
+                let path = if strip_cur_dir {
+                    entry.path().strip_prefix(".").unwrap_or(entry.path())
+                } else {
+                    entry.path()
+                };
+                if pattern.matches(path) {
+                    return Some(Ok(path.to_path_buf()));
+                }
+            },
+            Err(err) => return Some(Err(err))
+        }
+    }
+
+    None
+}
@@ -0,0 +1,1102 @@
+use core_collections::borrow::ToOwned;
+use ffi::OsString;
+use fmt;
+use io::{self, Read, Error, ErrorKind, IoSlice, IoSliceMut, Result, Write, Seek, SeekFrom};
+use os::unix::fs::OpenOptionsExt;
+use os::unix::io::{AsRawFd, FromRawFd, IntoRawFd, RawFd};
+use mem;
+use path::{PathBuf, Path};
+use string::String;
+use sync::atomic::{AtomicUsize, Ordering};
+use sys_common::AsInner;
+use sys_common::io as sys_io;
+use thread;
+use time::{Duration, SystemTime, UNIX_EPOCH};
+use vec::Vec;
+
+pub use self::copy_dir::{copy_dir_all, CopyOptions};
+pub use self::glob::{glob, Glob};
+pub use self::temp::{PersistError, TempDir, TempFile};
+pub use self::walk_dir::{walk_dir, WalkDir, WalkDirEntry};
+pub use self::watcher::{WatchEvent, Watcher};
+
+mod copy_dir;
+mod glob;
+mod temp;
+mod walk_dir;
+mod watcher;
+
+use syscall::{self, open, dup, close, fchmod, fcntl, fpath, frename, fstat, fstatvfs, ftruncate, futimens, getpid, kill,
+              lseek, fsync, mkdir, rmdir, unlink};
+use syscall::{ESRCH, O_RDWR, O_RDONLY, O_WRONLY, O_ACCMODE, O_APPEND, O_CREAT, O_EXCL, O_EXLOCK, O_NONBLOCK, O_SHLOCK, O_TRUNC, O_STAT, O_NOFOLLOW, O_SYMLINK, MODE_CHR, MODE_DIR, MODE_FIFO, MODE_FILE, MODE_PERM, MODE_SYMLINK, MODE_TYPE, SEEK_SET, SEEK_CUR, SEEK_END, SYS_LINK, F_GETFL, F_SETFL, Stat, StatVfs, TimeSpec};
+
+/// A Unix-style file
+pub struct File {
+    /// The id for the file
+    fd: usize,
+    /// The lock held by this file, encoded as described at `NO_LOCK`
+    lock: AtomicUsize,
+}
+
+impl File {
+    /// Open a new file using a path
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<File> {
+        open_with(path, O_RDONLY)
+    }
+
+    /// Create a new file using a path
+    pub fn create<P: AsRef<Path>>(path: P) -> Result<File> {
+        open_with(path, O_CREAT | O_RDWR | O_TRUNC | 0o664)
+    }
+
+    /// Duplicate the file
+    ///
+    /// The scheme interprets `buf`, which can be used to open a related resource, like accepting
+    /// a connection on a listening socket. To get an independent handle to the same file, use
+    /// `try_clone`.
+    pub fn dup(&self, buf: &[u8]) -> Result<File> {
+        dup(self.fd, buf).map(|fd| unsafe { File::from_raw_fd(fd) }).map_err(|x| Error::from_sys(x))
+    }
+
+    /// Create a new handle to the same file
+    ///
+    /// Unlike a duplicated descriptor on other platforms, the new handle has its own cursor, so
+    /// reading, writing or seeking through one handle does not move the other.
+    pub fn try_clone(&self) -> Result<File> {
+        self.dup(&[])
+    }
+
+    /// Switch the file in or out of non-blocking mode
+    pub fn set_nonblocking(&self, nonblocking: bool) -> Result<()> {
+        let flags = try!(fcntl(self.fd, F_GETFL, 0).map_err(|x| Error::from_sys(x)));
+        let flags = if nonblocking {
+            flags | O_NONBLOCK
+        } else {
+            flags & ! O_NONBLOCK
+        };
+        fcntl(self.fd, F_SETFL, flags).and(Ok(())).map_err(|x| Error::from_sys(x))
+    }
+
+    /// Get information about a file
+    pub fn metadata(&self) -> Result<Metadata> {
+        let mut stat = Stat::default();
+        try!(fstat(self.fd, &mut stat).map_err(|x| Error::from_sys(x)));
+        Ok(Metadata {
+            stat: stat
+        })
+    }
+
+    /// Get statistics about the filesystem containing the file
+    pub fn statvfs(&self) -> Result<FsStats> {
+        let mut stat = StatVfs::default();
+        try!(fstatvfs(self.fd, &mut stat).map_err(|x| Error::from_sys(x)));
+        Ok(FsStats {
+            stat: stat
+        })
+    }
+
+    /// Get the canonical path of the file
+    pub fn path(&self) -> Result<PathBuf> {
+        let mut buf: [u8; 4096] = [0; 4096];
+        match fpath(self.fd, &mut buf) {
+            Ok(count) => Ok(PathBuf::from(unsafe { String::from_utf8_unchecked(Vec::from(&buf[0..count])) })),
+            Err(err) => Err(Error::from_sys(err)),
+        }
+    }
+
+    /// Flush the file data and metadata
+    pub fn sync_all(&mut self) -> Result<()> {
+        fsync(self.fd).and(Ok(())).map_err(|x| Error::from_sys(x))
+    }
+
+    /// Flush the file data
+    pub fn sync_data(&mut self) -> Result<()> {
+        fsync(self.fd).and(Ok(())).map_err(|x| Error::from_sys(x))
+    }
+
+    /// Truncates the file
+    pub fn set_len(&self, size: u64) -> Result<()> {
+        ftruncate(self.fd, size as usize).and(Ok(())).map_err(|x| Error::from_sys(x))
+    }
+
+    /// Lock the file for shared use, blocking until the lock is acquired
+    ///
+    /// Locks are taken by opening the file again with `O_SHLOCK` or `O_EXLOCK`, and are released
+    /// on `unlock` or when the file is closed. Shared locks need this kernel support, so they fail
+    /// with `Unsupported` on schemes without it.
+    ///
+    /// Taking a lock of the other kind converts the lock held by the file. As with `flock`, the
+    /// conversion is not atomic: the held lock is released before the new one is taken.
+    pub fn lock_shared(&self) -> Result<()> {
+        self.take_lock(O_SHLOCK)
+    }
+
+    /// Lock the file for exclusive use, blocking until the lock is acquired
+    ///
+    /// On schemes without kernel locks, this falls back to creating a `.lock` file next to the
+    /// file, holding the id of the locking process. A lockfile whose process has exited is
+    /// removed, and otherwise it is checked again every 10 ms until it is gone.
+    pub fn lock_exclusive(&self) -> Result<()> {
+        self.take_lock(O_EXLOCK)
+    }
+
+    /// Try to lock the file for shared use, failing with `WouldBlock` if it is locked
+    pub fn try_lock_shared(&self) -> Result<()> {
+        self.take_lock(O_SHLOCK | O_NONBLOCK)
+    }
+
+    /// Try to lock the file for exclusive use, failing with `WouldBlock` if it is locked
+    pub fn try_lock_exclusive(&self) -> Result<()> {
+        self.take_lock(O_EXLOCK | O_NONBLOCK)
+    }
+
+    /// Take a lock using the given open flags, unless the file holds a lock of that kind already
+    fn take_lock(&self, flags: usize) -> Result<()> {
+        let exclusive = flags & O_EXLOCK == O_EXLOCK;
+        let held = self.lock.load(Ordering::SeqCst);
+        if held != NO_LOCK {
+            if lock_is_exclusive(held) == exclusive {
+                return Ok(());
+            }
+            try!(self.unlock());
+        }
+
+        let path = try!(self.path());
+        let state = match open(path.as_os_str().as_inner(), O_STAT | flags) {
+            Ok(fd) => lock_state(fd, exclusive, false),
+            Err(err) => {
+                let err = Error::from_sys(err);
+                match err.kind() {
+                    ErrorKind::Unsupported | ErrorKind::InvalidInput => if exclusive {
+                        let file = try!(lock_file(&path, flags & O_NONBLOCK == O_NONBLOCK));
+                        lock_state(file.into_raw_fd(), true, true)
+                    } else {
+                        return Err(Error::new(ErrorKind::Unsupported, "shared locks are not supported"));
+                    },
+                    _ => return Err(err)
+                }
+            }
+        };
+
+        // Another thread may have locked the file in the meantime, maybe with the other kind
+        if self.lock.compare_and_swap(NO_LOCK, state, Ordering::SeqCst) != NO_LOCK {
+            try!(release_lock(state));
+            return self.take_lock(flags);
+        }
+        Ok(())
+    }
+
+    /// Release a lock held on the file
+    pub fn unlock(&self) -> Result<()> {
+        match self.lock.swap(NO_LOCK, Ordering::SeqCst) {
+            NO_LOCK => Ok(()),
+            state => release_lock(state)
+        }
+    }
+
+    /// Change the permissions of the file
+    pub fn set_permissions(&self, perm: Permissions) -> Result<()> {
+        fchmod(self.fd, perm.mode & MODE_PERM).and(Ok(())).map_err(|x| Error::from_sys(x))
+    }
+
+    /// Set the access and modification times of the file
+    pub fn set_times(&self, accessed: SystemTime, modified: SystemTime) -> Result<()> {
+        let times = [try!(time_spec(accessed)), try!(time_spec(modified))];
+        futimens(self.fd, &times).and(Ok(())).map_err(|x| Error::from_sys(x))
+    }
+
+    /// Set the modification time of the file, keeping its access time
+    pub fn set_modified(&self, modified: SystemTime) -> Result<()> {
+        let accessed = try!(try!(self.metadata()).accessed());
+        self.set_times(accessed, modified)
+    }
+}
+
+/// The value of `File::lock` when the file holds no lock
+///
+/// Otherwise it holds the descriptor that keeps the lock, plus one, shifted up by two bits: the
+/// upper one is set if the lock is exclusive, and the lower one if the descriptor is a lockfile.
+const NO_LOCK: usize = 0;
+
+fn lock_state(fd: usize, exclusive: bool, lockfile: bool) -> usize {
+    (fd + 1) << 2 | (exclusive as usize) << 1 | lockfile as usize
+}
+
+fn lock_is_exclusive(state: usize) -> bool {
+    state & 2 == 2
+}
+
+/// Release a lock, given its value in `File::lock`
+fn release_lock(state: usize) -> Result<()> {
+    let file = unsafe { File::from_raw_fd((state >> 2) - 1) };
+    if state & 1 == 1 {
+        // The lockfile is found through its handle, in case the locked file was moved
+        try!(remove_file(try!(file.path())));
+    }
+    Ok(())
+}
+
+/// Lock `path` exclusively by creating `path.lock`, which holds the id of this process
+fn lock_file(path: &Path, nonblocking: bool) -> Result<File> {
+    let mut lock_path = path.as_os_str().to_os_string();
+    lock_path.push(".lock");
+    let lock_path = PathBuf::from(lock_path);
+
+    loop {
+        match OpenOptions::new().write(true).create_new(true).mode(0o644).open(&lock_path) {
+            Ok(mut file) => {
+                let pid = getpid().unwrap_or(0);
+                if let Err(err) = file.write_all(format!("{}\n", pid).as_bytes()) {
+                    let _ = remove_file(&lock_path);
+                    return Err(err);
+                }
+                return Ok(file);
+            },
+            Err(ref err) if err.kind() == ErrorKind::AlreadyExists => {
+                if try!(break_stale_lock(&lock_path)) {
+                    continue;
+                }
+                if nonblocking {
+                    return Err(Error::new(ErrorKind::WouldBlock, "file is locked"));
+                }
+                thread::sleep(Duration::from_millis(10));
+            },
+            Err(err) => return Err(err)
+        }
+    }
+}
+
+/// Remove a lockfile whose process has exited, returning whether it is gone
+///
+/// A lockfile without a process id is left alone, as its process may still be writing it.
+fn break_stale_lock(lock_path: &Path) -> Result<bool> {
+    let owner = |path: &Path| read_to_string(path).ok().and_then(|pid| pid.trim().parse::<usize>().ok());
+    let pid = match owner(lock_path) {
+        Some(pid) => pid,
+        None => return Ok(false)
+    };
+
+    match kill(pid, 0) {
+        Err(ref err) if err.errno == ESRCH => {
+            // Another process may have broken the lock and taken it in the meantime
+            if owner(lock_path) != Some(pid) {
+                return Ok(false);
+            }
+            match remove_file(lock_path) {
+                Ok(()) => Ok(true),
+                Err(ref err) if err.kind() == ErrorKind::NotFound => Ok(true),
+                Err(err) => Err(err)
+            }
+        },
+        _ => Ok(false)
+    }
+}
+
+/// Convert a `SystemTime` to the `TimeSpec` used by the kernel, which cannot be before the epoch
+fn time_spec(time: SystemTime) -> Result<TimeSpec> {
+    match time.duration_since(UNIX_EPOCH) {
+        Ok(duration) => Ok(TimeSpec {
+            tv_sec: duration.as_secs() as i64,
+            tv_nsec: duration.subsec_nanos() as i32,
+        }),
+        Err(_) => Err(Error::new(ErrorKind::InvalidInput, "time is before the epoch"))
+    }
+}
+
+impl AsRawFd for File {
+    fn as_raw_fd(&self) -> RawFd {
+        self.fd
+    }
+}
+
+impl FromRawFd for File {
+    unsafe fn from_raw_fd(fd: RawFd) -> Self {
+        File {
+            fd: fd,
+            lock: AtomicUsize::new(NO_LOCK)
+        }
+    }
+}
+
+impl IntoRawFd for File {
+    fn into_raw_fd(self) -> RawFd {
+        let _ = self.unlock();
+        let fd = self.fd;
+        mem::forget(self);
+        fd
+    }
+}
+
+impl Read for File {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        syscall::read(self.fd, buf).map_err(|x| Error::from_sys(x))
+    }
+
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut]) -> Result<usize> {
+        sys_io::read_vectored(self.fd, bufs)
+    }
+}
+
+impl Write for File {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        syscall::write(self.fd, buf).map_err(|x| Error::from_sys(x))
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        fsync(self.fd).and(Ok(())).map_err(|x| Error::from_sys(x))
+    }
+
+    fn write_vectored(&mut self, bufs: &[IoSlice]) -> Result<usize> {
+        sys_io::write_vectored(self.fd, bufs)
+    }
+}
+
+// Redox has no pread or pwrite, so positional I/O is done on a duplicate of the file, which has
+// its own offset. This leaves the offset of the file itself untouched.
+impl ::os::unix::fs::FileExt for File {
+    fn read_at(&self, buf: &mut [u8], offset: u64) -> Result<usize> {
+        let mut file = try!(self.dup(&[]));
+        try!(file.seek(SeekFrom::Start(offset)));
+        file.read(buf)
+    }
+
+    fn write_at(&self, buf: &[u8], offset: u64) -> Result<usize> {
+        let mut file = try!(self.dup(&[]));
+        try!(file.seek(SeekFrom::Start(offset)));
+        file.write(buf)
+    }
+}
+
+impl Seek for File {
+    /// Seek a given position
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
+        let (whence, offset) = match pos {
+            SeekFrom::Start(offset) => (SEEK_SET, offset as isize),
+            SeekFrom::Current(offset) => (SEEK_CUR, offset as isize),
+            SeekFrom::End(offset) => (SEEK_END, offset as isize),
+        };
+
+        lseek(self.fd, offset, whence).map(|position| position as u64).map_err(|x| Error::from_sys(x))
+    }
+}
+
+impl Drop for File {
+    fn drop(&mut self) {
+        let _ = self.unlock();
+        let _ = close(self.fd);
+    }
+}
+
+impl fmt::Debug for File {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut b = f.debug_struct("File");
+        b.field("fd", &self.fd);
+        if let Ok(path) = self.path() {
+            b.field("path", &path);
+        }
+        b.finish()
+    }
+}
+
+#[derive(Copy, Clone, Eq, PartialEq)]
+pub struct FileType {
+    mode: u16,
+}
+
+impl FileType {
+    fn is(&self, mode: u16) -> bool {
+        self.mode & MODE_TYPE == mode
+    }
+
+    pub fn is_dir(&self) -> bool {
+        self.is(MODE_DIR)
+    }
+
+    pub fn is_file(&self) -> bool {
+        self.is(MODE_FILE)
+    }
+
+    pub fn is_symlink(&self) -> bool {
+        self.is(MODE_SYMLINK)
+    }
+}
+
+// Redox has no block devices or sockets, so those are never reported
+impl ::os::unix::fs::FileTypeExt for FileType {
+    fn is_block_device(&self) -> bool { false }
+    fn is_char_device(&self) -> bool { self.is(MODE_CHR) }
+    fn is_fifo(&self) -> bool { self.is(MODE_FIFO) }
+    fn is_socket(&self) -> bool { false }
+}
+
+pub struct OpenOptions {
+    read: bool,
+    write: bool,
+    append: bool,
+    create: bool,
+    create_new: bool,
+    truncate: bool,
+    mode: u16,
+    custom_flags: u32,
+}
+
+impl OpenOptions {
+    pub fn new() -> OpenOptions {
+        OpenOptions {
+            read: false,
+            write: false,
+            append: false,
+            create: false,
+            create_new: false,
+            truncate: false,
+            mode: 0,
+            custom_flags: 0,
+        }
+    }
+
+    pub fn read(&mut self, read: bool) -> &mut OpenOptions {
+        self.read = read;
+        self
+    }
+
+    pub fn write(&mut self, write: bool) -> &mut OpenOptions {
+        self.write = write;
+        self
+    }
+
+    pub fn append(&mut self, append: bool) -> &mut OpenOptions {
+        self.append = append;
+        self
+    }
+
+    pub fn create(&mut self, create: bool) -> &mut OpenOptions {
+        self.create = create;
+        self
+    }
+
+    /// Create a new file, failing if it already exists
+    ///
+    /// If set, `create` and `truncate` are ignored.
+    pub fn create_new(&mut self, create_new: bool) -> &mut OpenOptions {
+        self.create_new = create_new;
+        self
+    }
+
+    pub fn truncate(&mut self, truncate: bool) -> &mut OpenOptions {
+        self.truncate = truncate;
+        self
+    }
+
+    pub fn open<P: AsRef<Path>>(&self, path: P) -> Result<File> {
+        let mut flags = 0;
+
+        if self.read && self.write {
+            flags |= O_RDWR;
+        } else if self.read {
+            flags |= O_RDONLY;
+        } else if self.write {
+            flags |= O_WRONLY;
+        }
+
+        if self.append {
+            flags |= O_APPEND;
+        }
+
+        if self.create_new {
+            flags |= O_CREAT | O_EXCL;
+        } else {
+            if self.create {
+                flags |= O_CREAT;
+            }
+
+            if self.truncate {
+                flags |= O_TRUNC;
+            }
+        }
+
+        flags |= (self.mode & MODE_PERM) as usize;
+
+        // The access mode is only controlled through read and write
+        flags |= self.custom_flags as usize & ! O_ACCMODE;
+
+        open_with(path, flags)
+    }
+}
+
+impl ::os::unix::fs::OpenOptionsExt for OpenOptions {
+    fn mode(&mut self, mode: u32) -> &mut Self {
+        self.mode = mode as u16;
+        self
+    }
+
+    fn custom_flags(&mut self, flags: u32) -> &mut Self {
+        self.custom_flags = flags;
+        self
+    }
+}
+
+pub struct Metadata {
+    stat: Stat
+}
+
+impl Metadata {
+    pub fn file_type(&self) -> FileType {
+        FileType {
+            mode: self.stat.st_mode
+        }
+    }
+
+    pub fn is_dir(&self) -> bool {
+        self.file_type().is_dir()
+    }
+
+    pub fn is_file(&self) -> bool {
+        self.file_type().is_file()
+    }
+
+    pub fn len(&self) -> u64 {
+        self.stat.st_size
+    }
+
+    pub fn permissions(&self) -> Permissions {
+        Permissions {
+            mode: self.stat.st_mode & MODE_PERM
+        }
+    }
+
+    /// The last modification time of the file
+    pub fn modified(&self) -> Result<SystemTime> {
+        Ok(UNIX_EPOCH + Duration::new(self.stat.st_mtime, self.stat.st_mtime_nsec))
+    }
+
+    /// The last access time of the file
+    pub fn accessed(&self) -> Result<SystemTime> {
+        Ok(UNIX_EPOCH + Duration::new(self.stat.st_atime, self.stat.st_atime_nsec))
+    }
+
+    /// The creation time of the file
+    ///
+    /// Redox does not record when a file was created, so this always fails with `Unsupported`.
+    /// `st_ctime` is the time of the last status change, available through `MetadataExt::ctime`.
+    pub fn created(&self) -> Result<SystemTime> {
+        Err(Error::new(ErrorKind::Unsupported, "creation time is not available on this platform"))
+    }
+}
+
+impl ::os::unix::fs::MetadataExt for Metadata {
+    fn dev(&self) -> u64 {
+        self.stat.st_dev
+    }
+
+    fn ino(&self) -> u64 {
+        self.stat.st_ino
+    }
+
+    fn mode(&self) -> u32 {
+        self.stat.st_mode as u32
+    }
+
+    fn nlink(&self) -> u64 {
+        self.stat.st_nlink as u64
+    }
+
+    fn uid(&self) -> u32 {
+        self.stat.st_uid
+    }
+
+    fn gid(&self) -> u32 {
+        self.stat.st_gid
+    }
+
+    fn size(&self) -> u64 {
+        self.stat.st_size
+    }
+
+    fn atime(&self) -> i64 {
+        self.stat.st_atime as i64
+    }
+
+    fn atime_nsec(&self) -> i64 {
+        self.stat.st_atime_nsec as i64
+    }
+
+    fn mtime(&self) -> i64 {
+        self.stat.st_mtime as i64
+    }
+
+    fn mtime_nsec(&self) -> i64 {
+        self.stat.st_mtime_nsec as i64
+    }
+
+    fn ctime(&self) -> i64 {
+        self.stat.st_ctime as i64
+    }
+
+    fn ctime_nsec(&self) -> i64 {
+        self.stat.st_ctime_nsec as i64
+    }
+
+    fn blksize(&self) -> u64 {
+        self.stat.st_blksize as u64
+    }
+
+    fn blocks(&self) -> u64 {
+        self.stat.st_blocks
+    }
+}
+
+/// Statistics about a filesystem, as returned by `fs::statvfs`
+///
+/// Redox does not report inode counts, so only block counts are available.
+#[derive(Copy, Clone, Debug)]
+pub struct FsStats {
+    stat: StatVfs
+}
+
+impl FsStats {
+    /// The size of a block in bytes
+    pub fn block_size(&self) -> u64 {
+        self.stat.f_bsize as u64
+    }
+
+    /// The total number of blocks
+    pub fn blocks(&self) -> u64 {
+        self.stat.f_blocks
+    }
+
+    /// The number of free blocks
+    pub fn blocks_free(&self) -> u64 {
+        self.stat.f_bfree
+    }
+
+    /// The number of free blocks available to unprivileged users
+    pub fn blocks_available(&self) -> u64 {
+        self.stat.f_bavail
+    }
+
+    /// The total size of the filesystem in bytes
+    pub fn total_space(&self) -> u64 {
+        self.blocks() * self.block_size()
+    }
+
+    /// The free space in bytes
+    pub fn free_space(&self) -> u64 {
+        self.blocks_free() * self.block_size()
+    }
+
+    /// The free space in bytes available to unprivileged users
+    pub fn available_space(&self) -> u64 {
+        self.blocks_available() * self.block_size()
+    }
+}
+
+pub struct Permissions {
+    mode: u16
+}
+
+impl Permissions {
+    pub fn readonly(&self) -> bool {
+        self.mode & 0o222 == 0
+    }
+
+    pub fn set_readonly(&mut self, readonly: bool) {
+        if readonly {
+            self.mode &= !0o222;
+        } else {
+            self.mode |= 0o222;
+        }
+    }
+}
+
+impl ::os::unix::fs::PermissionsExt for Permissions {
+    fn mode(&self) -> u32 {
+        self.mode as u32
+    }
+
+    fn set_mode(&mut self, mode: u32) {
+        self.mode = mode as u16;
+    }
+
+    fn from_mode(mode: u32) -> Self {
+        Permissions {
+            mode: mode as u16
+        }
+    }
+}
+
+pub struct DirEntry {
+    path: PathBuf,
+    name: OsString,
+    /// The file type, if the directory listing provided it
+    file_type: Option<FileType>,
+}
+
+impl DirEntry {
+    pub fn file_name(&self) -> OsString {
+        self.name.clone()
+    }
+
+    pub fn file_type(&self) -> Result<FileType> {
+        match self.file_type {
+            Some(file_type) => Ok(file_type),
+            None => symlink_metadata(&self.path).map(|metadata| metadata.file_type())
+        }
+    }
+
+    pub fn metadata(&self) -> Result<Metadata> {
+        metadata(&self.path)
+    }
+
+    pub fn path(&self) -> PathBuf {
+        self.path.clone()
+    }
+}
+
+/// An iterator over the entries of a directory
+///
+/// A directory is read as a list of names separated by newlines, where schemes may mark
+/// directories with a trailing `/`. Names that are not valid UTF-8 cannot be represented as an
+/// `OsString` on Redox, so they are yielded as `InvalidData` errors without ending the iteration.
+pub struct ReadDir {
+    path: PathBuf,
+    file: File,
+    buf: Vec<u8>,
+    eof: bool,
+}
+
+impl ReadDir {
+    /// Take the next name from the listing, reading more of it as needed
+    fn next_name(&mut self) -> Result<Option<Vec<u8>>> {
+        loop {
+            if let Some(i) = self.buf.iter().position(|&b| b == b'\n') {
+                let rest = self.buf.split_off(i + 1);
+                let mut name = mem::replace(&mut self.buf, rest);
+                name.pop();
+                return Ok(Some(name));
+            }
+
+            if self.eof {
+                if self.buf.is_empty() {
+                    return Ok(None);
+                }
+                return Ok(Some(mem::replace(&mut self.buf, Vec::new())));
+            }
+
+            let mut chunk = [0; 4096];
+            let count = try!(self.file.read(&mut chunk));
+            if count == 0 {
+                self.eof = true;
+            } else {
+                self.buf.extend_from_slice(&chunk[..count]);
+            }
+        }
+    }
+}
+
+impl Iterator for ReadDir {
+    type Item = Result<DirEntry>;
+    fn next(&mut self) -> Option<Result<DirEntry>> {
+        let mut name = match self.next_name() {
+            Ok(Some(name)) => name,
+            Ok(None) => return None,
+            Err(err) => return Some(Err(err))
+        };
+
+        let file_type = if name.len() > 1 && name.last() == Some(&b'/') {
+            name.pop();
+            Some(FileType { mode: MODE_DIR })
+        } else {
+            None
+        };
+
+        match String::from_utf8(name) {
+            Ok(name) => {
+                let mut path = self.path.clone();
+                path.push(&name);
+                Some(Ok(DirEntry {
+                    path: path,
+                    name: OsString::from(name),
+                    file_type: file_type
+                }))
+            },
+            Err(_) => Some(Err(Error::new(ErrorKind::InvalidData, "file name is not valid UTF-8")))
+        }
+    }
+}
+
+/// Read the entire contents of a file into a byte vector
+pub fn read<P: AsRef<Path>>(path: P) -> Result<Vec<u8>> {
+    let mut file = try!(File::open(path));
+    let len = file.metadata().map(|metadata| metadata.len() as usize).unwrap_or(0);
+    let mut bytes = Vec::with_capacity(len);
+    try!(file.read_to_end(&mut bytes));
+    Ok(bytes)
+}
+
+/// Read the entire contents of a file into a string
+pub fn read_to_string<P: AsRef<Path>>(path: P) -> Result<String> {
+    let mut file = try!(File::open(path));
+    let len = file.metadata().map(|metadata| metadata.len() as usize).unwrap_or(0);
+    let mut string = String::with_capacity(len);
+    try!(file.read_to_string(&mut string));
+    Ok(string)
+}
+
+/// Write a slice as the entire contents of a file, creating or truncating it
+pub fn write<P: AsRef<Path>, C: AsRef<[u8]>>(path: P, contents: C) -> Result<()> {
+    try!(File::create(path)).write_all(contents.as_ref())
+}
+
+/// Replace the contents of a file atomically
+///
+/// The data is written to a temporary file in the same directory, flushed with `sync_all`, and
+/// then renamed over `path`. Readers see either the old or the new contents, never a mix.
+///
+/// The permissions of an existing file are kept. A new file gets the same mode as with
+/// `File::create`.
+pub fn write_atomic<P: AsRef<Path>, C: AsRef<[u8]>>(path: P, contents: C) -> Result<()> {
+    let path = path.as_ref();
+    let name = match path.file_name() {
+        Some(name) => name.to_string_lossy().into_owned(),
+        None => return Err(Error::new(ErrorKind::InvalidInput, "path has no file name"))
+    };
+    let pid = getpid().unwrap_or(0);
+    let mode = metadata(path).map(|metadata| metadata.permissions().mode).unwrap_or(0o664);
+
+    let mut i = 0;
+    let (tmp_path, mut file) = loop {
+        let tmp_path = path.with_file_name(format!(".{}.{}.{}.tmp", name, pid, i));
+        match OpenOptions::new().write(true).create_new(true).mode(mode as u32).open(&tmp_path) {
+            Ok(file) => break (tmp_path, file),
+            Err(ref err) if err.kind() == ErrorKind::AlreadyExists => i += 1,
+            Err(err) => return Err(err)
+        }
+    };
+
+    let res = file.write_all(contents.as_ref())
+                  .and_then(|_| file.sync_all())
+                  .and_then(|_| rename(&tmp_path, path));
+    if res.is_err() {
+        let _ = remove_file(&tmp_path);
+    }
+    res
+}
+
+/// Open a file with the given flags
+///
+/// Metadata is read and changed through handles opened with `O_STAT`, which needs neither read
+/// nor write permission, so a file without read permission can still be inspected and changed.
+fn open_with<P: AsRef<Path>>(path: P, flags: usize) -> Result<File> {
+    let path_str = path.as_ref().as_os_str().as_inner();
+    open(path_str, flags).map(|fd| unsafe { File::from_raw_fd(fd) }).map_err(|x| Error::from_sys(x))
+}
+
+/// Find the canonical path of a file
+pub fn canonicalize<P: AsRef<Path>>(path: P) -> Result<PathBuf> {
+    match File::open(path) {
+        Ok(file) => {
+            match file.path() {
+                Ok(realpath) => Ok(realpath),
+                Err(err) => Err(err)
+            }
+        },
+        Err(err) => Err(err)
+    }
+}
+
+/// Get information about a file
+pub fn metadata<P: AsRef<Path>>(path: P) -> Result<Metadata> {
+    try!(open_with(path, O_STAT)).metadata()
+}
+
+/// Get information about a file without following symlinks
+pub fn symlink_metadata<P: AsRef<Path>>(path: P) -> Result<Metadata> {
+    try!(open_with(path, O_STAT | O_NOFOLLOW)).metadata()
+}
+
+/// Get statistics about the filesystem containing a path
+pub fn statvfs<P: AsRef<Path>>(path: P) -> Result<FsStats> {
+    try!(open_with(path, O_STAT)).statvfs()
+}
+
+/// Change the permissions of a file
+pub fn set_permissions<P: AsRef<Path>>(path: P, perm: Permissions) -> Result<()> {
+    try!(open_with(path, O_STAT)).set_permissions(perm)
+}
+
+/// Set the modification time of a file, keeping its access time
+pub fn set_modified<P: AsRef<Path>>(path: P, modified: SystemTime) -> Result<()> {
+    try!(open_with(path, O_STAT)).set_modified(modified)
+}
+
+/// Read the target of a symbolic link
+///
+/// Fails with `InvalidInput` if the path is not a symbolic link.
+pub fn read_link<P: AsRef<Path>>(path: P) -> Result<PathBuf> {
+    // Opening with O_SYMLINK only avoids following a link, so any other file opens as well
+    let mut file = try!(open_with(path, O_SYMLINK | O_RDONLY));
+    if ! try!(file.metadata()).file_type().is_symlink() {
+        return Err(Error::new(ErrorKind::InvalidInput, "not a symbolic link"));
+    }
+    let mut buf = Vec::new();
+    try!(file.read_to_end(&mut buf));
+    match String::from_utf8(buf) {
+        Ok(target) => Ok(PathBuf::from(target)),
+        Err(_) => Err(Error::new(ErrorKind::InvalidData, "link target is not valid UTF-8"))
+    }
+}
+
+/// Create a new directory, using a path
+/// The default mode of the directory is 775
+pub fn create_dir<P: AsRef<Path>>(path: P) -> Result<()> {
+    DirBuilder::new().create(path)
+}
+
+/// Recursively create a directory and all of its parent components if they are missing.
+pub fn create_dir_all<P: AsRef<Path>>(path: P) -> Result<()> {
+    DirBuilder::new().recursive(true).create(path)
+}
+
+/// A builder used to create directories
+pub struct DirBuilder {
+    recursive: bool,
+    mode: u16,
+}
+
+impl DirBuilder {
+    /// Create a builder for non-recursive creation, with mode 775
+    pub fn new() -> DirBuilder {
+        DirBuilder {
+            recursive: false,
+            mode: 0o775,
+        }
+    }
+
+    /// Create missing parents as well, succeeding if the directory already exists
+    pub fn recursive(&mut self, recursive: bool) -> &mut DirBuilder {
+        self.recursive = recursive;
+        self
+    }
+
+    /// Create the directory at `path`
+    pub fn create<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        if self.recursive {
+            self.create_dir_all(path.as_ref())
+        } else {
+            self.mkdir(path.as_ref())
+        }
+    }
+
+    fn mkdir(&self, path: &Path) -> Result<()> {
+        let path_str = path.as_os_str().as_inner();
+        mkdir(path_str, self.mode & MODE_PERM).and(Ok(())).map_err(|x| Error::from_sys(x))
+    }
+
+    fn create_dir_all(&self, path: &Path) -> Result<()> {
+        if path == Path::new("") {
+            return Ok(());
+        }
+
+        // Try the directory first, so that concurrent creators do not race on a check
+        match self.mkdir(path) {
+            Ok(()) => return Ok(()),
+            Err(ref err) if err.kind() == ErrorKind::NotFound => (),
+            Err(_) if path.is_dir() => return Ok(()),
+            Err(err) => return Err(err)
+        }
+
+        match path.parent() {
+            Some(parent) => try!(self.create_dir_all(parent)),
+            None => return Err(Error::new(ErrorKind::Other, "failed to create whole tree"))
+        }
+
+        match self.mkdir(path) {
+            Ok(()) => Ok(()),
+            Err(_) if path.is_dir() => Ok(()),
+            Err(err) => Err(err)
+        }
+    }
+}
+
+impl ::os::unix::fs::DirBuilderExt for DirBuilder {
+    fn mode(&mut self, mode: u32) -> &mut Self {
+        self.mode = mode as u16;
+        self
+    }
+}
+
+/// Copy the contents of one file to another
+///
+/// The permissions and timestamps of the source are copied to the destination, unless its scheme
+/// does not support changing them.
+pub fn copy<P: AsRef<Path>, Q: AsRef<Path>>(from: P, to: Q) -> Result<u64> {
+    let mut infile = try!(File::open(from));
+    let metadata = try!(infile.metadata());
+    let mut outfile = try!(OpenOptions::new().write(true).create(true).truncate(true)
+                                             .mode(metadata.permissions().mode as u32)
+                                             .open(to));
+    // The mode only applies to new files, so an existing destination is changed explicitly
+    try!(unless_unsupported(outfile.set_permissions(metadata.permissions())));
+    let count = try!(io::copy(&mut infile, &mut outfile));
+    try!(unless_unsupported(outfile.set_times(try!(metadata.accessed()), try!(metadata.modified()))));
+    Ok(count)
+}
+
+/// Treat a metadata change the scheme does not support as done
+fn unless_unsupported(res: Result<()>) -> Result<()> {
+    match res {
+        Err(ref err) if err.kind() == ErrorKind::Unsupported => Ok(()),
+        res => res
+    }
+}
+
+/// Rename a file or directory to a new name, atomically replacing the destination if it exists
+pub fn rename<P: AsRef<Path>, Q: AsRef<Path>>(from: P, to: Q) -> Result<()> {
+    let to_str = to.as_ref().as_os_str().as_inner();
+    let file = try!(open_with(from, O_STAT | O_NOFOLLOW));
+    frename(file.fd, to_str).and(Ok(())).map_err(|x| Error::from_sys(x))
+}
+
+/// Create a new hard link at `dst` to the file at `src`
+///
+/// Fails with `AlreadyExists` if `dst` exists, and with `CrossesDevices` if the paths are on
+/// different schemes.
+pub fn hard_link<P: AsRef<Path>, Q: AsRef<Path>>(src: P, dst: Q) -> Result<()> {
+    // The link binding passes bare pointers, but the kernel reads both paths as pointer and length
+    let src_str = src.as_ref().as_os_str().as_inner();
+    let dst_str = dst.as_ref().as_os_str().as_inner();
+    unsafe {
+        syscall::syscall4(SYS_LINK, src_str.as_ptr() as usize, src_str.len(), dst_str.as_ptr() as usize, dst_str.len())
+    }.and(Ok(())).map_err(|x| Error::from_sys(x))
+}
+
+/// Return an iterator over the entries within a directory
+pub fn read_dir<P: AsRef<Path>>(path: P) -> Result<ReadDir> {
+    let path_buf = path.as_ref().to_owned();
+    File::open(&path_buf).map(|file| ReadDir { path: path_buf, file: file, buf: Vec::new(), eof: false })
+}
+
+/// Removes an existing, empty directory
+pub fn remove_dir<P: AsRef<Path>>(path: P) -> Result<()> {
+    let path_str = path.as_ref().as_os_str().as_inner();
+    rmdir(path_str).and(Ok(())).map_err(|x| Error::from_sys(x))
+}
+
+/// Removes a directory at this path, after removing all its contents. Use carefully!
+pub fn remove_dir_all<P: AsRef<Path>>(path: P) -> Result<()> {
+    for child in try!(read_dir(&path)) {
+        let child = try!(child);
+        if try!(child.file_type()).is_dir() {
+            try!(remove_dir_all(&child.path()));
+        } else {
+            try!(remove_file(&child.path()));
+        }
+    }
+    remove_dir(path)
+}
+
+/// Removes a file from the filesystem
+pub fn remove_file<P: AsRef<Path>>(path: P) -> Result<()> {
+    let path_str = path.as_ref().as_os_str().as_inner();
+    unlink(path_str).and(Ok(())).map_err(|x| Error::from_sys(x))
+}
@@ -0,0 +1,248 @@
+use env;
+use error::Error as StdError;
+use fmt;
+use io::{Error, ErrorKind, IoSlice, IoSliceMut, Read, Result, Seek, SeekFrom, Write};
+use mem;
+use os::unix::fs::{DirBuilderExt, OpenOptionsExt};
+use path::{Path, PathBuf};
+use result;
+use time::{SystemTime, UNIX_EPOCH};
+
+use syscall::getpid;
+
+use super::{remove_dir_all, remove_file, rename, DirBuilder, File, OpenOptions};
+
+/// The number of names tried before giving up on creating a temporary entry
+const NUM_RETRIES: u32 = 1 << 16;
+
+/// Get a random number from the `rand:` scheme
+///
+/// If the scheme is not available, the time and process id are mixed instead. Collisions are
+/// still handled by the caller, as entries are created with `create_new`.
+fn random() -> u64 {
+    let mut bytes = [0; 8];
+    if let Ok(mut file) = File::open("rand:") {
+        if file.read_exact(&mut bytes).is_ok() {
+            return unsafe { mem::transmute(bytes) };
+        }
+    }
+
+    let time = SystemTime::now().duration_since(UNIX_EPOCH)
+                                .map(|dur| dur.as_secs() ^ (dur.subsec_nanos() as u64) << 32)
+                                .unwrap_or(0);
+    let pid = getpid().unwrap_or(0) as u64;
+    (time ^ pid.rotate_left(17)).wrapping_mul(0x9E37_79B9_7F4A_7C15)
+}
+
+/// Try to create a uniquely named entry in `dir` using `create`
+fn create_unique<T, F: FnMut(&Path) -> Result<T>>(dir: &Path, mut create: F) -> Result<(PathBuf, T)> {
+    for _ in 0..NUM_RETRIES {
+        let path = dir.join(format!(".tmp{:016x}", random()));
+        match create(&path) {
+            Ok(value) => return Ok((path, value)),
+            Err(ref err) if err.kind() == ErrorKind::AlreadyExists => (),
+            Err(err) => return Err(err)
+        }
+    }
+    Err(Error::new(ErrorKind::AlreadyExists, "too many temporary files exist"))
+}
+
+/// The error returned when a temporary file or directory cannot be persisted
+///
+/// It gives back the temporary entry, which is still removed when dropped, so it can be
+/// persisted somewhere else or its contents can be recovered.
+pub struct PersistError<T> {
+    /// The error that made the rename fail
+    pub error: Error,
+    /// The temporary entry that was not persisted
+    pub temp: T,
+}
+
+impl<T> From<PersistError<T>> for Error {
+    fn from(err: PersistError<T>) -> Error {
+        err.error
+    }
+}
+
+impl<T> fmt::Debug for PersistError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("PersistError").field("error", &self.error).finish()
+    }
+}
+
+impl<T> fmt::Display for PersistError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "failed to persist temporary entry: {}", self.error)
+    }
+}
+
+impl<T> StdError for PersistError<T> {
+    fn description(&self) -> &str {
+        self.error.description()
+    }
+}
+
+fn default_dir() -> PathBuf {
+    env::temp_dir().unwrap_or(PathBuf::from("/tmp"))
+}
+
+/// A uniquely named temporary file, which is removed when dropped
+pub struct TempFile {
+    path: PathBuf,
+    file: Option<File>,
+}
+
+impl TempFile {
+    /// Create a new temporary file in `env::temp_dir()`
+    pub fn new() -> Result<TempFile> {
+        TempFile::new_in(default_dir())
+    }
+
+    /// Create a new temporary file in `dir`, readable and writable only by its owner
+    pub fn new_in<P: AsRef<Path>>(dir: P) -> Result<TempFile> {
+        let (path, file) = try!(create_unique(dir.as_ref(), |path| {
+            OpenOptions::new().read(true).write(true).create_new(true).mode(0o600).open(path)
+        }));
+        Ok(TempFile {
+            path: path,
+            file: Some(file)
+        })
+    }
+
+    /// The path of the file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// The open file
+    pub fn as_file(&self) -> &File {
+        self.file.as_ref().unwrap()
+    }
+
+    /// The open file, mutably
+    pub fn as_file_mut(&mut self) -> &mut File {
+        self.file.as_mut().unwrap()
+    }
+
+    /// Move the file to `path` and keep it, returning the open file
+    ///
+    /// If the file cannot be moved, it is returned in the error.
+    pub fn persist<P: AsRef<Path>>(mut self, path: P) -> result::Result<File, PersistError<TempFile>> {
+        let res = rename(&self.path, path);
+        match res {
+            Ok(()) => Ok(self.file.take().unwrap()),
+            Err(err) => Err(PersistError {
+                error: err,
+                temp: self
+            })
+        }
+    }
+}
+
+impl Read for TempFile {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        self.as_file_mut().read(buf)
+    }
+
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut]) -> Result<usize> {
+        self.as_file_mut().read_vectored(bufs)
+    }
+}
+
+impl Write for TempFile {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        self.as_file_mut().write(buf)
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        self.as_file_mut().flush()
+    }
+
+    fn write_vectored(&mut self, bufs: &[IoSlice]) -> Result<usize> {
+        self.as_file_mut().write_vectored(bufs)
+    }
+}
+
+impl Seek for TempFile {
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
+        self.as_file_mut().seek(pos)
+    }
+}
+
+impl Drop for TempFile {
+    fn drop(&mut self) {
+        if self.file.take().is_some() {
+            let _ = remove_file(&self.path);
+        }
+    }
+}
+
+impl fmt::Debug for TempFile {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("TempFile").field("path", &self.path).finish()
+    }
+}
+
+/// A uniquely named temporary directory, which is removed with its contents when dropped
+pub struct TempDir {
+    path: Option<PathBuf>,
+}
+
+impl TempDir {
+    /// Create a new temporary directory in `env::temp_dir()`
+    pub fn new() -> Result<TempDir> {
+        TempDir::new_in(default_dir())
+    }
+
+    /// Create a new temporary directory in `dir`, accessible only by its owner
+    pub fn new_in<P: AsRef<Path>>(dir: P) -> Result<TempDir> {
+        let (path, _) = try!(create_unique(dir.as_ref(), |path| DirBuilder::new().mode(0o700).create(path)));
+        Ok(TempDir {
+            path: Some(path)
+        })
+    }
+
+    /// The path of the directory
+    pub fn path(&self) -> &Path {
+        self.path.as_ref().unwrap()
+    }
+
+    /// Move the directory to `path` and keep it
+    ///
+    /// If the directory cannot be moved, it is returned in the error.
+    pub fn persist<P: AsRef<Path>>(mut self, path: P) -> result::Result<PathBuf, PersistError<TempDir>> {
+        let res = rename(self.path(), &path);
+        match res {
+            Ok(()) => {
+                self.path = None;
+                Ok(path.as_ref().to_path_buf())
+            },
+            Err(err) => Err(PersistError {
+                error: err,
+                temp: self
+            })
+        }
+    }
+
+    /// Remove the directory and its contents, reporting any error
+    pub fn close(mut self) -> Result<()> {
+        match self.path.take() {
+            Some(path) => remove_dir_all(path),
+            None => Ok(())
+        }
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        if let Some(path) = self.path.take() {
+            let _ = remove_dir_all(path);
+        }
+    }
+}
+
+impl fmt::Debug for TempDir {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("TempDir").field("path", &self.path).finish()
+    }
+}
@@ -0,0 +1,277 @@
+use ffi::OsStr;
+use io::{Error, ErrorKind, Result};
+use path::{Path, PathBuf};
+use usize;
+use vec::{self, Vec};
+
+use super::{canonicalize, metadata, read_dir, symlink_metadata, DirEntry, FileType, Metadata};
+
+/// Return an iterator that recursively walks the directory at `path`
+///
+/// The directory itself is yielded first, at depth 0. Errors are yielded in place of the entries
+/// they concern, and the walk continues past them.
+pub fn walk_dir<P: AsRef<Path>>(path: P) -> WalkDir {
+    WalkDir {
+        start: Some(path.as_ref().to_path_buf()),
+        min_depth: 0,
+        max_depth: usize::MAX,
+        follow_links: false,
+        sort: false,
+        contents_first: false,
+        stack: Vec::new(),
+    }
+}
+
+/// An entry yielded by `WalkDir`
+pub struct WalkDirEntry {
+    path: PathBuf,
+    file_type: FileType,
+    depth: usize,
+    follow_link: bool,
+}
+
+impl WalkDirEntry {
+    fn new(entry: DirEntry, depth: usize, follow_links: bool) -> Result<WalkDirEntry> {
+        // The type comes from the listing where possible, so only followed links are opened
+        let mut file_type = try!(entry.file_type());
+        let path = entry.path();
+        let follow_link = follow_links && file_type.is_symlink();
+        if follow_link {
+            file_type = try!(metadata(&path)).file_type();
+        }
+        Ok(WalkDirEntry {
+            path: path,
+            file_type: file_type,
+            depth: depth,
+            follow_link: follow_link
+        })
+    }
+
+    /// The path of the entry, starting with the path the walk was started at
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// The file name of the entry
+    pub fn file_name(&self) -> &OsStr {
+        self.path.file_name().unwrap_or(self.path.as_os_str())
+    }
+
+    /// The type of the entry, which is the type of the target if the entry is a followed link
+    pub fn file_type(&self) -> FileType {
+        self.file_type
+    }
+
+    /// Get information about the entry, following it if it is a followed link
+    pub fn metadata(&self) -> Result<Metadata> {
+        if self.follow_link {
+            metadata(&self.path)
+        } else {
+            symlink_metadata(&self.path)
+        }
+    }
+
+    /// Whether the entry is a symbolic link that was followed
+    pub fn path_is_symlink(&self) -> bool {
+        self.follow_link
+    }
+
+    /// The depth of the entry, relative to the path the walk was started at
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+}
+
+/// A directory being walked
+struct Frame {
+    /// The path of the directory, until its entries are read by the first `next` after it is
+    /// handled, so that `skip_current_dir` saves reading them
+    unread: Option<PathBuf>,
+    /// The depth of the entries
+    depth: usize,
+    entries: vec::IntoIter<Result<WalkDirEntry>>,
+    /// The directory, if it is yielded after its contents
+    dir: Option<WalkDirEntry>,
+    /// The canonical path of the directory, if links are followed
+    canonical: Option<PathBuf>,
+}
+
+/// A recursive iterator over a directory, created by `walk_dir`
+pub struct WalkDir {
+    start: Option<PathBuf>,
+    min_depth: usize,
+    max_depth: usize,
+    follow_links: bool,
+    sort: bool,
+    contents_first: bool,
+    stack: Vec<Frame>,
+}
+
+impl WalkDir {
+    /// Only yield entries at least `depth` levels below the starting path
+    pub fn min_depth(mut self, depth: usize) -> WalkDir {
+        self.min_depth = depth;
+        self
+    }
+
+    /// Do not descend more than `depth` levels below the starting path
+    pub fn max_depth(mut self, depth: usize) -> WalkDir {
+        self.max_depth = depth;
+        self
+    }
+
+    /// Follow symbolic links, yielding an error instead of walking a directory twice in a loop
+    pub fn follow_links(mut self, follow_links: bool) -> WalkDir {
+        self.follow_links = follow_links;
+        self
+    }
+
+    /// Yield the entries of each directory sorted by file name
+    pub fn sort(mut self, sort: bool) -> WalkDir {
+        self.sort = sort;
+        self
+    }
+
+    /// Yield directories after their contents, instead of before
+    pub fn contents_first(mut self, contents_first: bool) -> WalkDir {
+        self.contents_first = contents_first;
+        self
+    }
+
+    /// Skip the rest of the directory whose entries are currently being yielded
+    ///
+    /// Called right after a directory is yielded, this skips the contents of that directory
+    /// without reading them.
+    pub fn skip_current_dir(&mut self) {
+        if let Some(frame) = self.stack.pop() {
+            // Still yield the directory itself, when it would come after its contents
+            if let Some(dir) = frame.dir {
+                let entries = if dir.depth >= self.min_depth { vec![Ok(dir)] } else { Vec::new() };
+                self.stack.push(Frame {
+                    unread: None,
+                    depth: dir.depth + 1,
+                    entries: entries.into_iter(),
+                    dir: None,
+                    canonical: None,
+                });
+            }
+        }
+    }
+
+    /// Handle an entry, returning it if it should be yielded now
+    fn handle(&mut self, entry: WalkDirEntry) -> Option<Result<WalkDirEntry>> {
+        if entry.file_type.is_dir() && entry.depth < self.max_depth {
+            let canonical = if self.follow_links {
+                match canonicalize(&entry.path) {
+                    Ok(canonical) => {
+                        if self.stack.iter().any(|frame| frame.canonical.as_ref() == Some(&canonical)) {
+                            return Some(Err(Error::new(ErrorKind::Other, "filesystem loop found")));
+                        }
+                        Some(canonical)
+                    },
+                    Err(err) => return Some(Err(err))
+                }
+            } else {
+                None
+            };
+
+            let entry_path = entry.path.clone();
+            let entry_depth = entry.depth;
+            let yield_now = ! self.contents_first && entry.depth >= self.min_depth;
+            let (now, dir) = if yield_now {
+                (Some(Ok(entry)), None)
+            } else if self.contents_first {
+                (None, Some(entry))
+            } else {
+                (None, None)
+            };
+
+            self.stack.push(Frame {
+                unread: Some(entry_path),
+                depth: entry_depth + 1,
+                entries: Vec::new().into_iter(),
+                dir: dir,
+                canonical: canonical,
+            });
+
+            return now;
+        }
+
+        if entry.depth >= self.min_depth {
+            Some(Ok(entry))
+        } else {
+            None
+        }
+    }
+}
+
+impl Iterator for WalkDir {
+    type Item = Result<WalkDirEntry>;
+
+    fn next(&mut self) -> Option<Result<WalkDirEntry>> {
+        if let Some(start) = self.start.take() {
+            // The starting path is always followed, so a link to a directory is walked
+            let entry = match metadata(&start) {
+                Ok(metadata) => WalkDirEntry {
+                    follow_link: symlink_metadata(&start).map(|link| link.file_type().is_symlink()).unwrap_or(false),
+                    path: start,
+                    file_type: metadata.file_type(),
+                    depth: 0,
+                },
+                Err(err) => return Some(Err(err))
+            };
+            if let Some(res) = self.handle(entry) {
+                return Some(res);
+            }
+        }
+
+        let (follow_links, sort) = (self.follow_links, self.sort);
+        loop {
+            let next = match self.stack.last_mut() {
+                Some(frame) => {
+                    if let Some(path) = frame.unread.take() {
+                        frame.entries = match read_entries(&path, frame.depth, follow_links, sort) {
+                            Ok(entries) => entries.into_iter(),
+                            Err(err) => vec![Err(err)].into_iter()
+                        };
+                    }
+                    frame.entries.next()
+                },
+                None => return None
+            };
+
+            match next {
+                Some(Ok(entry)) => if let Some(res) = self.handle(entry) {
+                    return Some(res);
+                },
+                Some(Err(err)) => return Some(Err(err)),
+                None => {
+                    let frame = self.stack.pop().unwrap();
+                    if let Some(dir) = frame.dir {
+                        if dir.depth >= self.min_depth {
+                            return Some(Ok(dir));
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Read the entries of the directory at `path`, which are at `depth`
+fn read_entries(path: &Path, depth: usize, follow_links: bool, sort: bool) -> Result<Vec<Result<WalkDirEntry>>> {
+    let mut entries = Vec::new();
+    for entry in try!(read_dir(path)) {
+        entries.push(entry.and_then(|entry| WalkDirEntry::new(entry, depth, follow_links)));
+    }
+
+    if sort {
+        entries.sort_by(|a, b| {
+            let a = a.as_ref().ok().map(|entry| entry.file_name());
+            let b = b.as_ref().ok().map(|entry| entry.file_name());
+            a.cmp(&b)
+        });
+    }
+
+    Ok(entries)
+}
@@ -0,0 +1,164 @@
+use collections::{BTreeMap, BTreeSet, VecDeque};
+use ffi::OsString;
+use io::{Error, ErrorKind, Read, Result};
+use os::unix::fs::MetadataExt;
+use os::unix::io::AsRawFd;
+use path::{Path, PathBuf};
+use sync::mpsc::{channel, Receiver};
+use thread;
+
+use syscall::{fevent, Event, EVENT_NONE, EVENT_READ};
+
+use super::{metadata, read_dir, File};
+
+/// A change reported by a `Watcher`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum WatchEvent {
+    /// A file was created in a watched directory
+    Create(PathBuf),
+    /// A watched file was modified
+    Modify(PathBuf),
+    /// A watched file, or a file in a watched directory, was removed
+    Remove(PathBuf),
+}
+
+/// The last seen state of a watched path
+#[derive(PartialEq, Eq)]
+enum Snapshot {
+    File(i64, i64, u64),
+    Dir(BTreeSet<OsString>),
+    Missing,
+}
+
+impl Snapshot {
+    fn take(path: &Path) -> Snapshot {
+        match metadata(path) {
+            Ok(ref metadata) if metadata.is_dir() => {
+                let mut names = BTreeSet::new();
+                if let Ok(entries) = read_dir(path) {
+                    for entry in entries {
+                        if let Ok(entry) = entry {
+                            names.insert(entry.file_name());
+                        }
+                    }
+                }
+                Snapshot::Dir(names)
+            },
+            Ok(metadata) => Snapshot::File(metadata.mtime(), metadata.mtime_nsec(), metadata.len()),
+            Err(_) => Snapshot::Missing
+        }
+    }
+}
+
+struct Watch {
+    path: PathBuf,
+    /// The file registered for events, kept open for as long as the path is watched
+    file: File,
+    snapshot: Snapshot,
+}
+
+/// Watches files and directories for changes
+///
+/// Each watched path is kept open and registered with the `event:` scheme using `fevent`. When
+/// its scheme reports it as readable, the path is compared against its last seen state to work
+/// out which changes happened. Paths on schemes that do not send events cannot be watched.
+pub struct Watcher {
+    events: File,
+    watches: BTreeMap<usize, Watch>,
+    pending: VecDeque<WatchEvent>,
+}
+
+impl Watcher {
+    /// Create a watcher with no watched paths
+    pub fn new() -> Result<Watcher> {
+        Ok(Watcher {
+            events: try!(File::open("event:")),
+            watches: BTreeMap::new(),
+            pending: VecDeque::new(),
+        })
+    }
+
+    /// Start watching a file or directory
+    pub fn watch<P: AsRef<Path>>(&mut self, path: P) -> Result<()> {
+        let path = path.as_ref().to_path_buf();
+        let file = try!(File::open(&path));
+        let fd = file.as_raw_fd();
+        try!(fevent(fd, EVENT_READ).map_err(|x| Error::from_sys(x)));
+        let snapshot = Snapshot::take(&path);
+        self.watches.insert(fd, Watch {
+            path: path,
+            file: file,
+            snapshot: snapshot,
+        });
+        Ok(())
+    }
+
+    /// Stop watching a file or directory
+    pub fn unwatch<P: AsRef<Path>>(&mut self, path: P) -> Result<()> {
+        let fd = match self.watches.iter().find(|&(_, watch)| watch.path.as_path() == path.as_ref()) {
+            Some((&fd, _)) => fd,
+            None => return Err(Error::new(ErrorKind::NotFound, "path is not watched"))
+        };
+        let watch = self.watches.remove(&fd).unwrap();
+        let _ = fevent(watch.file.as_raw_fd(), EVENT_NONE);
+        Ok(())
+    }
+
+    /// Block until the next change to a watched path
+    pub fn next_event(&mut self) -> Result<WatchEvent> {
+        loop {
+            if let Some(event) = self.pending.pop_front() {
+                return Ok(event);
+            }
+
+            let mut event = Event::default();
+            try!(self.events.read_exact(&mut event));
+            if let Some(watch) = self.watches.get_mut(&event.id) {
+                let snapshot = Snapshot::take(&watch.path);
+                diff(&watch.path, &watch.snapshot, &snapshot, &mut self.pending);
+                watch.snapshot = snapshot;
+            }
+        }
+    }
+
+    /// Move the watcher to a new thread, which sends every change through a channel
+    ///
+    /// The thread stops after sending an error, or when it fails to send an event because the
+    /// receiver was dropped. Dropping the receiver does not wake the thread, so it keeps waiting,
+    /// and keeps every watched path open, until the next change arrives. If no change may come,
+    /// use `next_event` on a thread that can be stopped instead.
+    pub fn into_channel(mut self) -> Receiver<Result<WatchEvent>> {
+        let (tx, rx) = channel();
+        thread::spawn(move || {
+            loop {
+                let res = self.next_event();
+                let stop = res.is_err();
+                if tx.send(res).is_err() || stop {
+                    break;
+                }
+            }
+        });
+        rx
+    }
+}
+
+/// Turn the difference between two snapshots of a path into events
+fn diff(path: &Path, old: &Snapshot, new: &Snapshot, events: &mut VecDeque<WatchEvent>) {
+    match (old, new) {
+        (&Snapshot::Dir(ref old_names), &Snapshot::Dir(ref new_names)) => {
+            for name in new_names.difference(old_names) {
+                events.push_back(WatchEvent::Create(path.join(name)));
+            }
+            for name in old_names.difference(new_names) {
+                events.push_back(WatchEvent::Remove(path.join(name)));
+            }
+        },
+        (_, &Snapshot::Missing) => if *old != Snapshot::Missing {
+            events.push_back(WatchEvent::Remove(path.to_path_buf()));
+        },
+        (&Snapshot::Missing, _) => events.push_back(WatchEvent::Create(path.to_path_buf())),
+        _ => if old != new {
+            events.push_back(WatchEvent::Modify(path.to_path_buf()));
+        }
+    }
+}
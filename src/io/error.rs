@@ -17,7 +17,7 @@ use option::Option::{self, None};
 use result;
 
 use syscall::Error as SysError;
-use syscall::{ENOENT, EACCES, EEXIST, EINVAL, STR_ERROR};
+use syscall::{EAGAIN, ENOENT, EACCES, EEXIST, EINVAL, ENOSYS, EOPNOTSUPP, EPERM, EXDEV, STR_ERROR};
 
 /// A specialized [`Result`](../result/enum.Result.html) type for I/O
 /// operations.
@@ -108,9 +108,12 @@ impl Error {
         match &self.repr {
             &Repr::Os(errno) => match errno {
                 ENOENT => ErrorKind::NotFound,
-                EACCES => ErrorKind::PermissionDenied,
+                EACCES | EPERM => ErrorKind::PermissionDenied,
                 EEXIST => ErrorKind::AlreadyExists,
                 EINVAL => ErrorKind::InvalidInput,
+                EXDEV => ErrorKind::CrossesDevices,
+                EAGAIN => ErrorKind::WouldBlock,
+                ENOSYS | EOPNOTSUPP => ErrorKind::Unsupported,
                 _ => ErrorKind::Other
             },
             &Repr::Custom(ref c) => c.kind,
@@ -197,6 +200,10 @@ pub enum ErrorKind {
     ///
     /// Interrupted operations can typically be retried.
     Interrupted,
+    /// A link or rename was attempted across filesystems or schemes.
+    CrossesDevices,
+    /// The operation is not supported, for example by the scheme of a file.
+    Unsupported,
     /// Any I/O error not part of this list.
     Other,
 
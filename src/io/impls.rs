@@ -10,7 +10,7 @@
 
 use boxed::Box;
 use cmp;
-use io::{self, SeekFrom, Read, Write, Seek, BufRead, Error, ErrorKind};
+use io::{self, SeekFrom, Read, Write, Seek, BufRead, Error, ErrorKind, IoSlice, IoSliceMut};
 use fmt;
 use mem;
 use string::String;
@@ -39,6 +39,10 @@ impl<'a, R: Read + ?Sized> Read for &'a mut R {
     fn read_exact(&mut self, buf: &mut [u8]) -> io::Result<()> {
         (**self).read_exact(buf)
     }
+    #[inline]
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut]) -> io::Result<usize> {
+        (**self).read_vectored(bufs)
+    }
 }
 impl<'a, W: Write + ?Sized> Write for &'a mut W {
     #[inline]
@@ -56,6 +60,10 @@ impl<'a, W: Write + ?Sized> Write for &'a mut W {
     fn write_fmt(&mut self, fmt: fmt::Arguments) -> io::Result<()> {
         (**self).write_fmt(fmt)
     }
+    #[inline]
+    fn write_vectored(&mut self, bufs: &[IoSlice]) -> io::Result<usize> {
+        (**self).write_vectored(bufs)
+    }
 }
 impl<'a, S: Seek + ?Sized> Seek for &'a mut S {
     #[inline]
@@ -99,6 +107,10 @@ impl<R: Read + ?Sized> Read for Box<R> {
     fn read_exact(&mut self, buf: &mut [u8]) -> io::Result<()> {
         (**self).read_exact(buf)
     }
+    #[inline]
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut]) -> io::Result<usize> {
+        (**self).read_vectored(bufs)
+    }
 }
 impl<W: Write + ?Sized> Write for Box<W> {
     #[inline]
@@ -116,6 +128,10 @@ impl<W: Write + ?Sized> Write for Box<W> {
     fn write_fmt(&mut self, fmt: fmt::Arguments) -> io::Result<()> {
         (**self).write_fmt(fmt)
     }
+    #[inline]
+    fn write_vectored(&mut self, bufs: &[IoSlice]) -> io::Result<usize> {
+        (**self).write_vectored(bufs)
+    }
 }
 impl<S: Seek + ?Sized> Seek for Box<S> {
     #[inline]
@@ -163,6 +179,20 @@ impl<'a> Read for &'a [u8] {
         *self = b;
         Ok(())
     }
+
+    #[inline]
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut]) -> io::Result<usize> {
+        // Reading from memory never blocks, so every buffer can be filled
+        let mut total = 0;
+        for buf in bufs.iter_mut() {
+            let n = try!(self.read(buf));
+            total += n;
+            if self.is_empty() {
+                break;
+            }
+        }
+        Ok(total)
+    }
 }
 
 impl<'a> BufRead for &'a [u8] {
@@ -249,10 +249,12 @@ pub use self::util::{copy, sink, Sink, empty, Empty, repeat, Repeat};
 use prelude::v1::{Vec, String};
 use fmt;
 use cmp;
+use ops::{Deref, DerefMut};
 use str;
 use result;
 use error as std_error;
 use syscall::{read, write, fsync};
+use sys_common::io as sys_io;
 
 pub mod prelude;
 mod buffered;
@@ -599,6 +601,21 @@ pub trait Read {
         }
     }
 
+    /// Like `read`, except that it reads into a slice of buffers.
+    ///
+    /// Data is copied to fill each buffer in order, with the final buffer
+    /// written to possibly being only partially filled. The default
+    /// implementation calls `read` once, with the first non-empty buffer, as
+    /// a second read could block even though data was already returned.
+    /// Implementors backed by a file descriptor override it to use a single
+    /// system call.
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut]) -> Result<usize> {
+        match bufs.iter_mut().find(|buf| !buf.is_empty()) {
+            Some(buf) => self.read(buf),
+            None => Ok(0),
+        }
+    }
+
     /// Creates a "by reference" adaptor for this instance of `Read`.
     ///
     /// The returned adaptor also implements `Read` and will simply borrow this
@@ -902,6 +919,28 @@ pub trait Write {
     /// ```
     fn flush(&mut self) -> Result<()>;
 
+    /// Like `write`, except that it writes from a slice of buffers.
+    ///
+    /// Data is copied from each buffer in order, with the final buffer read
+    /// from possibly being only partially consumed. The default
+    /// implementation calls `write` for each buffer, stopping at the first
+    /// short write. Implementors backed by a file descriptor override it to
+    /// use a single system call.
+    fn write_vectored(&mut self, bufs: &[IoSlice]) -> Result<usize> {
+        let mut total = 0;
+        for buf in bufs.iter() {
+            if buf.is_empty() {
+                continue;
+            }
+            let n = try!(self.write(buf));
+            total += n;
+            if n < buf.len() {
+                break;
+            }
+        }
+        Ok(total)
+    }
+
     /// Attempts to write an entire buffer into this write.
     ///
     /// This method will continuously call `write` while there is more data to
@@ -1417,6 +1456,60 @@ pub trait BufRead: Read {
     }
 }
 
+/// A buffer type used with `Write::write_vectored`.
+pub struct IoSlice<'a>(&'a [u8]);
+
+impl<'a> IoSlice<'a> {
+    /// Creates a new `IoSlice` wrapping a byte slice.
+    pub fn new(buf: &'a [u8]) -> IoSlice<'a> {
+        IoSlice(buf)
+    }
+}
+
+impl<'a> Deref for IoSlice<'a> {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        self.0
+    }
+}
+
+impl<'a> fmt::Debug for IoSlice<'a> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(self.0, fmt)
+    }
+}
+
+/// A buffer type used with `Read::read_vectored`.
+pub struct IoSliceMut<'a>(&'a mut [u8]);
+
+impl<'a> IoSliceMut<'a> {
+    /// Creates a new `IoSliceMut` wrapping a byte slice.
+    pub fn new(buf: &'a mut [u8]) -> IoSliceMut<'a> {
+        IoSliceMut(buf)
+    }
+}
+
+impl<'a> Deref for IoSliceMut<'a> {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        self.0
+    }
+}
+
+impl<'a> DerefMut for IoSliceMut<'a> {
+    fn deref_mut(&mut self) -> &mut [u8] {
+        self.0
+    }
+}
+
+impl<'a> fmt::Debug for IoSliceMut<'a> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(&*self.0, fmt)
+    }
+}
+
 /// A `Write` adaptor which will write data to multiple locations.
 ///
 /// This struct is generally created by calling [`broadcast()`][broadcast] on a
@@ -1831,6 +1924,42 @@ mod tests {
                    io::ErrorKind::UnexpectedEof);
     }
 
+    #[test]
+    fn read_vectored() {
+        let mut c = &b"123456"[..];
+        let mut a = [0; 2];
+        let mut b = [0; 8];
+        {
+            let mut bufs = [io::IoSliceMut::new(&mut a), io::IoSliceMut::new(&mut b)];
+            assert_eq!(c.read_vectored(&mut bufs).unwrap(), 6);
+        }
+        assert_eq!(&a, b"12");
+        assert_eq!(&b[..4], b"3456");
+    }
+
+    #[test]
+    fn read_vectored_default() {
+        // The default implementation reads into the first non-empty buffer only
+        let mut c = Cursor::new(&b"123456"[..]);
+        let mut a = [0; 2];
+        let mut b = [0; 8];
+        {
+            let mut bufs = [io::IoSliceMut::new(&mut []), io::IoSliceMut::new(&mut a),
+                            io::IoSliceMut::new(&mut b)];
+            assert_eq!(c.read_vectored(&mut bufs).unwrap(), 2);
+        }
+        assert_eq!(&a, b"12");
+        assert_eq!(b, [0; 8]);
+    }
+
+    #[test]
+    fn write_vectored() {
+        let mut c = Cursor::new(Vec::new());
+        let bufs = [io::IoSlice::new(b"12"), io::IoSlice::new(b""), io::IoSlice::new(b"34")];
+        assert_eq!(c.write_vectored(&bufs).unwrap(), 4);
+        assert_eq!(c.get_ref(), b"1234");
+    }
+
     #[test]
     fn read_exact_slice() {
         let mut buf = [0; 4];
@@ -1903,6 +2032,10 @@ impl Read for Stdin {
     fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
         read(0, buf).map_err(|x| Error::from_sys(x))
     }
+
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut]) -> Result<usize> {
+        sys_io::read_vectored(0, bufs)
+    }
 }
 
 /// Standard Input lock
@@ -1914,6 +2047,10 @@ impl<'a> Read for StdinLock<'a> {
     fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
         read(0, buf).map_err(|x| Error::from_sys(x))
     }
+
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut]) -> Result<usize> {
+        sys_io::read_vectored(0, bufs)
+    }
 }
 
 impl<'a> StdinLock<'a> {
@@ -1959,6 +2096,10 @@ impl Write for Stdout {
     fn flush(&mut self) -> Result<()> {
         fsync(1).map_err(|x| Error::from_sys(x)).and(Ok(()))
     }
+
+    fn write_vectored(&mut self, bufs: &[IoSlice]) -> Result<usize> {
+        sys_io::write_vectored(1, bufs)
+    }
 }
 
 /// Standard Output lock
@@ -1974,6 +2115,10 @@ impl<'a> Write for StdoutLock<'a> {
     fn flush(&mut self) -> Result<()> {
         fsync(1).map_err(|x| Error::from_sys(x)).and(Ok(()))
     }
+
+    fn write_vectored(&mut self, bufs: &[IoSlice]) -> Result<usize> {
+        sys_io::write_vectored(1, bufs)
+    }
 }
 
 /// Standard Error
@@ -1999,6 +2144,10 @@ impl Write for Stderr {
     fn flush(&mut self) -> Result<()> {
         fsync(2).map_err(|x| Error::from_sys(x)).and(Ok(()))
     }
+
+    fn write_vectored(&mut self, bufs: &[IoSlice]) -> Result<usize> {
+        sys_io::write_vectored(2, bufs)
+    }
 }
 
 /// Standard Error lock
@@ -2014,6 +2163,10 @@ impl<'a> Write for StderrLock<'a> {
     fn flush(&mut self) -> Result<()> {
         fsync(2).map_err(|x| Error::from_sys(x)).and(Ok(()))
     }
+
+    fn write_vectored(&mut self, bufs: &[IoSlice]) -> Result<usize> {
+        sys_io::write_vectored(2, bufs)
+    }
 }
 
 #[allow(unused_must_use)]
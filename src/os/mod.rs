@@ -1 +1,2 @@
+pub mod redox;
 pub mod unix;
@@ -0,0 +1,129 @@
+use fs::File;
+use io::{Error, Result};
+use ops::{Deref, DerefMut};
+use os::unix::io::AsRawFd;
+use slice;
+
+use syscall::{close, dup, fmap, fsync, funmap};
+
+/// A mapping of a file into memory
+struct Mapping {
+    fd: usize,
+    addr: usize,
+    len: usize,
+}
+
+impl Mapping {
+    unsafe fn new(file: &File, offset: u64, len: usize) -> Result<Mapping> {
+        // The mapping keeps its own descriptor, so it may outlive the file
+        let fd = try!(dup(file.as_raw_fd(), &[]).map_err(|x| Error::from_sys(x)));
+        let addr = if len == 0 {
+            0
+        } else {
+            match fmap(fd, offset as usize, len) {
+                Ok(addr) => addr,
+                Err(err) => {
+                    let _ = close(fd);
+                    return Err(Error::from_sys(err));
+                }
+            }
+        };
+        Ok(Mapping {
+            fd: fd,
+            addr: addr,
+            len: len
+        })
+    }
+
+    fn as_slice(&self) -> &[u8] {
+        if self.len == 0 {
+            &[]
+        } else {
+            unsafe { slice::from_raw_parts(self.addr as *const u8, self.len) }
+        }
+    }
+
+    fn as_mut_slice(&mut self) -> &mut [u8] {
+        if self.len == 0 {
+            &mut []
+        } else {
+            unsafe { slice::from_raw_parts_mut(self.addr as *mut u8, self.len) }
+        }
+    }
+}
+
+impl Drop for Mapping {
+    fn drop(&mut self) {
+        if self.len > 0 {
+            let _ = unsafe { funmap(self.addr) };
+        }
+        let _ = close(self.fd);
+    }
+}
+
+/// A read only memory map of a file, created using `fmap`
+///
+/// The file is unmapped when the map is dropped.
+pub struct Mmap {
+    inner: Mapping,
+}
+
+impl Mmap {
+    /// Map `len` bytes of `file`, starting at `offset`
+    ///
+    /// This is unsafe, as the contents of the map change if the file is modified, by this or
+    /// any other process.
+    pub unsafe fn new(file: &File, offset: u64, len: usize) -> Result<Mmap> {
+        Mapping::new(file, offset, len).map(|inner| Mmap { inner: inner })
+    }
+}
+
+impl Deref for Mmap {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        self.inner.as_slice()
+    }
+}
+
+unsafe impl Send for Mmap {}
+unsafe impl Sync for Mmap {}
+
+/// A writable memory map of a file, created using `fmap`
+///
+/// The file must be opened for writing. The file is unmapped when the map is dropped.
+pub struct MmapMut {
+    inner: Mapping,
+}
+
+impl MmapMut {
+    /// Map `len` bytes of `file`, starting at `offset`
+    ///
+    /// This is unsafe, as the contents of the map change if the file is modified, by this or
+    /// any other process.
+    pub unsafe fn new(file: &File, offset: u64, len: usize) -> Result<MmapMut> {
+        Mapping::new(file, offset, len).map(|inner| MmapMut { inner: inner })
+    }
+
+    /// Write modifications of the map back to the file
+    pub fn flush(&self) -> Result<()> {
+        fsync(self.inner.fd).and(Ok(())).map_err(|x| Error::from_sys(x))
+    }
+}
+
+impl Deref for MmapMut {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        self.inner.as_slice()
+    }
+}
+
+impl DerefMut for MmapMut {
+    fn deref_mut(&mut self) -> &mut [u8] {
+        self.inner.as_mut_slice()
+    }
+}
+
+unsafe impl Send for MmapMut {}
+unsafe impl Sync for MmapMut {}
@@ -0,0 +1,8 @@
+use process::Command;
+
+pub trait CommandExt {
+    /// Place the child in a namespace that only contains the listed schemes.
+    ///
+    /// The scheme of the program itself, usually `file`, must be listed for it to be executed.
+    fn schemes(&mut self, schemes: &[&str]) -> &mut Command;
+}
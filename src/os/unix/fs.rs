@@ -1,3 +1,11 @@
+use io::{Error, ErrorKind, Result, Write};
+use fs::{remove_file, OpenOptions};
+use os::unix::io::AsRawFd;
+use path::Path;
+use sys_common::AsInner;
+
+use syscall::{fchown as sys_fchown, fstat, O_STAT, O_SYMLINK, Stat};
+
 pub trait FileTypeExt {
     fn is_block_device(&self) -> bool;
     fn is_char_device(&self) -> bool;
@@ -5,15 +13,62 @@ pub trait FileTypeExt {
     fn is_socket(&self) -> bool;
 }
 
+pub trait DirBuilderExt {
+    /// Set the mode to create new directories with
+    fn mode(&mut self, mode: u32) -> &mut Self;
+}
+
+pub trait FileExt {
+    /// Read from the given offset, without changing the offset of the file
+    fn read_at(&self, buf: &mut [u8], offset: u64) -> Result<usize>;
+
+    /// Write at the given offset, without changing the offset of the file
+    fn write_at(&self, buf: &[u8], offset: u64) -> Result<usize>;
+
+    /// Read exactly enough bytes to fill `buf`, starting at the given offset
+    fn read_exact_at(&self, mut buf: &mut [u8], mut offset: u64) -> Result<()> {
+        while !buf.is_empty() {
+            match self.read_at(buf, offset) {
+                Ok(0) => break,
+                Ok(n) => {
+                    let tmp = buf;
+                    buf = &mut tmp[n..];
+                    offset += n as u64;
+                },
+                Err(ref e) if e.kind() == ErrorKind::Interrupted => {}
+                Err(e) => return Err(e),
+            }
+        }
+        if !buf.is_empty() {
+            Err(Error::new(ErrorKind::UnexpectedEof, "failed to fill whole buffer"))
+        } else {
+            Ok(())
+        }
+    }
+}
+
 pub trait MetadataExt {
+    fn dev(&self) -> u64;
+    fn ino(&self) -> u64;
     fn mode(&self) -> u32;
+    fn nlink(&self) -> u64;
     fn uid(&self) -> u32;
     fn gid(&self) -> u32;
     fn size(&self) -> u64;
+    fn atime(&self) -> i64;
+    fn atime_nsec(&self) -> i64;
+    fn mtime(&self) -> i64;
+    fn mtime_nsec(&self) -> i64;
+    fn ctime(&self) -> i64;
+    fn ctime_nsec(&self) -> i64;
+    fn blksize(&self) -> u64;
+    fn blocks(&self) -> u64;
 }
 
 pub trait OpenOptionsExt {
     fn mode(&mut self, mode: u32) -> &mut Self;
+    /// Pass additional flags, such as `O_NONBLOCK` or `O_NOFOLLOW`, to the open call
+    fn custom_flags(&mut self, flags: u32) -> &mut Self;
 }
 
 pub trait PermissionsExt {
@@ -21,3 +76,30 @@ pub trait PermissionsExt {
     fn set_mode(&mut self, mode: u32);
     fn from_mode(mode: u32) -> Self;
 }
+
+/// Create a new symbolic link at `dst` pointing to `src`
+pub fn symlink<P: AsRef<Path>, Q: AsRef<Path>>(src: P, dst: Q) -> Result<()> {
+    let mut file = try!(OpenOptions::new().write(true).create_new(true).mode(0o777)
+                                          .custom_flags(O_SYMLINK as u32).open(&dst));
+    let res = file.write_all(src.as_ref().as_os_str().as_inner().as_bytes());
+    if res.is_err() {
+        // Do not leave an empty or truncated link behind
+        drop(file);
+        let _ = remove_file(&dst);
+    }
+    res
+}
+
+/// Change the owner and group of a file, leaving either unchanged if `None`
+pub fn chown<P: AsRef<Path>>(path: P, uid: Option<u32>, gid: Option<u32>) -> Result<()> {
+    let file = try!(OpenOptions::new().custom_flags(O_STAT as u32).open(path));
+    fchown(&file, uid, gid)
+}
+
+/// Change the owner and group of an open file, leaving either unchanged if `None`
+pub fn fchown<F: AsRawFd>(file: &F, uid: Option<u32>, gid: Option<u32>) -> Result<()> {
+    let fd = file.as_raw_fd();
+    let mut stat = Stat::default();
+    try!(fstat(fd, &mut stat).map_err(|x| Error::from_sys(x)));
+    sys_fchown(fd, uid.unwrap_or(stat.st_uid), gid.unwrap_or(stat.st_gid)).and(Ok(())).map_err(|x| Error::from_sys(x))
+}
@@ -254,7 +254,7 @@ mod platform {
 
 #[cfg(redox)]
 mod platform {
-    use super::Prefix;
+    use super::{os_str_as_u8_slice, u8_slice_as_os_str, Prefix};
     use ffi::OsStr;
 
     #[inline]
@@ -267,8 +267,17 @@ mod platform {
         b == b'/'
     }
 
-    pub fn parse_prefix(_: &OsStr) -> Option<Prefix> {
-        None
+    pub fn parse_prefix<'a>(path: &'a OsStr) -> Option<Prefix> {
+        // A path starting with `scheme:` refers to a scheme, as long as the
+        // colon comes before the first separator
+        let path = os_str_as_u8_slice(path);
+        match path.iter().position(|&b| b == b':' || is_sep_byte(b)) {
+            Some(idx) if idx > 0 && path[idx] == b':' => unsafe {
+                // Safe because the slice ends at an ASCII byte
+                Some(Prefix::Scheme(u8_slice_as_os_str(&path[..idx])))
+            },
+            _ => None
+        }
     }
 
     pub const MAIN_SEP_STR: &'static str = "/";
@@ -279,13 +288,16 @@ mod platform {
 // Windows Prefixes
 ////////////////////////////////////////////////////////////////////////////////
 
-/// Path prefixes (Windows only).
+/// Path prefixes (Windows and Redox only).
 ///
 /// Windows uses a variety of path styles, including references to drive
 /// volumes (like `C:`), network shared folders (like `\\server\share`) and
 /// others. In addition, some path prefixes are "verbatim", in which case
 /// `/` is *not* treated as a separator and essentially no normalization is
 /// performed.
+///
+/// On Redox, a path may start with the scheme it refers to, like `file:` or
+/// `tcp:`.
 #[derive(Copy, Clone, Debug, Hash, PartialOrd, Ord, PartialEq, Eq)]
 
 pub enum Prefix<'a> {
@@ -318,6 +330,10 @@ pub enum Prefix<'a> {
     /// Prefix `C:` for the given disk drive.
 
     Disk( u8),
+
+    /// Prefix `scheme:` for the given scheme (Redox only).
+
+    Scheme( &'a OsStr),
 }
 
 impl<'a> Prefix<'a> {
@@ -348,6 +364,7 @@ impl<'a> Prefix<'a> {
             },
             DeviceNS(x) => 4 + os_str_len(x),
             Disk(_) => 2,
+            Scheme(x) => os_str_len(x) + 1,
         }
 
     }
@@ -371,6 +388,14 @@ impl<'a> Prefix<'a> {
         }
     }
 
+    #[inline]
+    fn is_scheme(&self) -> bool {
+        match *self {
+            Prefix::Scheme(_) => true,
+            _ => false,
+        }
+    }
+
     #[inline]
     fn has_implicit_root(&self) -> bool {
         !self.is_drive()
@@ -1066,11 +1091,12 @@ impl PathBuf {
         // in general, a separator is needed if the rightmost byte is not a separator
         let mut need_sep = self.as_mut_vec().last().map(|c| !is_sep_byte(*c)).unwrap_or(false);
 
-        // in the special case of `C:` on Windows, do *not* add a separator
+        // in the special case of `C:` on Windows, or a bare `scheme:` on
+        // Redox, do *not* add a separator
         {
             let comps = self.components();
             if comps.prefix_len() > 0 && comps.prefix_len() == comps.path.len() &&
-               comps.prefix.unwrap().is_drive() {
+               (comps.prefix.unwrap().is_drive() || comps.prefix.unwrap().is_scheme()) {
                 need_sep = false
             }
         }
@@ -1446,6 +1472,9 @@ impl Path {
     /// * On Unix, a path is absolute if it starts with the root, so
     /// `is_absolute` and `has_root` are equivalent.
     ///
+    /// * On Redox, a path is also absolute if it starts with a scheme, like
+    /// `file:` or `tcp:`.
+    ///
     /// * On Windows, a path is absolute if it has a prefix and starts with the
     /// root: `c:\windows` is absolute, while `c:temp` and `\temp` are not. In
     /// other words, `path.is_absolute() == path.prefix().is_some() && path.has_root()`.
@@ -1478,17 +1507,38 @@ impl Path {
 
     /// Returns the *prefix* of a path, if any.
     ///
-    /// Prefixes are relevant only for Windows paths, and consist of volumes
-    /// like `C:`, UNC prefixes like `\\server`, and others described in more
-    /// detail in `std::os::windows::PathExt`.
+    /// Prefixes are relevant only for Windows and Redox paths. On Windows they
+    /// consist of volumes like `C:`, UNC prefixes like `\\server`, and others
+    /// described in more detail in `std::os::windows::PathExt`. On Redox they
+    /// name the scheme of a path, like `file:`.
     pub fn prefix(&self) -> Option<Prefix> {
         self.components().prefix
     }
 
+    /// Returns the scheme of a path, if any, without the trailing `:`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::ffi::OsStr;
+    /// use std::path::Path;
+    ///
+    /// assert_eq!(Path::new("tcp:127.0.0.1:80").scheme(), Some(OsStr::new("tcp")));
+    /// assert_eq!(Path::new("/home/user").scheme(), None);
+    /// ```
+    pub fn scheme(&self) -> Option<&OsStr> {
+        match self.prefix() {
+            Some(Prefix::Scheme(scheme)) => Some(scheme),
+            _ => None
+        }
+    }
+
     /// A path has a root if the body of the path begins with the directory separator.
     ///
     /// * On Unix, a path has a root if it begins with `/`.
     ///
+    /// * On Redox, a path has a root if it begins with `/` or a scheme.
+    ///
     /// * On Windows, a path has a root if it:
     ///     * has no prefix and begins with a separator, e.g. `\\windows`
     ///     * has a prefix followed by a separator, e.g. `c:\windows` but not `c:windows`
@@ -1634,6 +1684,134 @@ impl Path {
         iter_after(self.components().rev(), child.components().rev()).is_some()
     }
 
+    /// Resolves `.` and `..` components lexically, without accessing the
+    /// file system.
+    ///
+    /// Each `..` removes the component before it. A `..` directly after the
+    /// root stays at the root, while leading `..` components of a relative
+    /// path are kept. If no components are left, the result is `.`.
+    ///
+    /// Unlike `canonicalize`, symbolic links are not resolved, so `a/link/..`
+    /// becomes `a` even if `link` points somewhere else.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::path::{Path, PathBuf};
+    ///
+    /// assert_eq!(Path::new("/usr/./lib/../bin").normalize(), PathBuf::from("/usr/bin"));
+    /// assert_eq!(Path::new("../foo/../bar").normalize(), PathBuf::from("../bar"));
+    /// ```
+
+    pub fn normalize(&self) -> PathBuf {
+        let mut comps: Vec<Component> = Vec::new();
+        for comp in self.components() {
+            match comp {
+                Component::CurDir => (),
+                Component::ParentDir => match comps.last() {
+                    Some(&Component::Normal(_)) => {
+                        comps.pop();
+                    },
+                    Some(&Component::RootDir) | Some(&Component::Prefix(_)) => (),
+                    _ => comps.push(comp),
+                },
+                _ => comps.push(comp),
+            }
+        }
+
+        let has_physical_root = self.components().has_physical_root;
+        let mut buf = PathBuf::new();
+        for comp in comps {
+            match comp {
+                // Pushing these would replace the path, so they are appended
+                // as-is. The root is only written if it was in the original.
+                Component::Prefix(_) => buf.inner.push(comp.as_os_str()),
+                Component::RootDir => if has_physical_root {
+                    buf.inner.push(MAIN_SEP_STR);
+                },
+                _ => buf.push(comp.as_os_str()),
+            }
+        }
+        if buf.as_os_str().is_empty() {
+            buf.push(".");
+        }
+        buf
+    }
+
+    /// Computes the relative path that leads from `base` to `self`.
+    ///
+    /// Both paths are normalized first. The result only contains `..` and
+    /// normal components, so `base.join(result)` normalizes to the same path
+    /// as `self`. If the paths are equal, the result is `.`.
+    ///
+    /// Returns `None` if only one of the paths is absolute, if they have
+    /// different prefixes or roots, or if `base` starts with more `..`
+    /// components than `self`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::path::{Path, PathBuf};
+    ///
+    /// let path = Path::new("/usr/lib/libc.a");
+    /// assert_eq!(path.relative_to("/usr/bin"), Some(PathBuf::from("../lib/libc.a")));
+    /// assert_eq!(path.relative_to("lib"), None);
+    /// ```
+
+    pub fn relative_to<P: AsRef<Path>>(&self, base: P) -> Option<PathBuf> {
+        self._relative_to(base.as_ref())
+    }
+
+    fn _relative_to(&self, base: &Path) -> Option<PathBuf> {
+        let path = self.normalize();
+        let base = base.normalize();
+        if path.is_absolute() != base.is_absolute() {
+            return None;
+        }
+
+        // A normalized path only has a `.` component if it is `.` itself
+        let mut path_comps = path.components().filter(|&c| c != Component::CurDir);
+        let mut base_comps = base.components().filter(|&c| c != Component::CurDir);
+        let mut comps: Vec<Component> = Vec::new();
+        loop {
+            match (path_comps.next(), base_comps.next()) {
+                (None, None) => break,
+                (Some(a), None) => {
+                    comps.push(a);
+                    comps.extend(path_comps.by_ref());
+                    break;
+                },
+                (None, Some(Component::Normal(_))) => comps.push(Component::ParentDir),
+                (None, Some(_)) => return None,
+                (Some(a), Some(b)) if a == b => (),
+                (Some(a @ Component::Normal(_)), Some(Component::Normal(_))) |
+                (Some(a @ Component::ParentDir), Some(Component::Normal(_))) => {
+                    // Leave the rest of `base`, then follow the rest of `self`
+                    comps.push(Component::ParentDir);
+                    for b in base_comps.by_ref() {
+                        match b {
+                            Component::Normal(_) => comps.push(Component::ParentDir),
+                            _ => return None,
+                        }
+                    }
+                    comps.push(a);
+                    comps.extend(path_comps.by_ref());
+                    break;
+                },
+                _ => return None,
+            }
+        }
+
+        let mut buf = PathBuf::new();
+        for comp in comps {
+            buf.push(comp.as_os_str());
+        }
+        if buf.as_os_str().is_empty() {
+            buf.push(".");
+        }
+        Some(buf)
+    }
+
     /// Extracts the stem (non-extension) portion of `self.file_name()`.
     ///
     /// The stem is:
@@ -2141,6 +2319,441 @@ impl Error for StripPrefixError {
     fn description(&self) -> &str { "prefix not found" }
 }
 
+////////////////////////////////////////////////////////////////////////////////
+// Glob patterns
+////////////////////////////////////////////////////////////////////////////////
+
+/// A compiled glob pattern, which can be matched against paths.
+///
+/// The pattern is matched one path component at a time:
+///
+/// * `?` matches any single character.
+/// * `*` matches any sequence of characters, including none.
+/// * `[abc]` matches any of the given characters, and `[a-z]` any character
+///   in the given range. `[!a-z]` and `[^a-z]` match any other character. A
+///   `]` directly after the opening bracket is matched literally, which can
+///   also be used to escape the other special characters, like `[*]`.
+/// * `**` as a whole component matches any number of directories, including
+///   none.
+/// * `{a,b}` matches either of the comma-separated alternatives, which may
+///   contain separators, special characters and nested alternatives.
+///
+/// Names starting with `.` are only matched by a component starting with a
+/// literal `.`, and are never crossed by `**`. A pattern only matches paths
+/// with the same prefix and root, so a relative pattern never matches an
+/// absolute path. Separators cannot be matched by a character class.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::Pattern;
+///
+/// let pattern = Pattern::new("src/**/*.{rs,toml}").unwrap();
+///
+/// assert!(pattern.matches("src/lib.rs"));
+/// assert!(pattern.matches("src/sys/common/mod.rs"));
+/// assert!(!pattern.matches("src/lib.c"));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Pattern {
+    original: String,
+    alternatives: Vec<Alternative>,
+}
+
+/// One of the patterns a `Pattern` expands to after its `{a,b}` groups
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Alternative {
+    /// The prefix and root, as written
+    root: String,
+    segments: Vec<Segment>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Segment {
+    /// A `.` or `..` component, matched literally
+    Exact(String),
+    /// `**`, matching any number of directories
+    AnyDirs,
+    /// Any other component
+    Name(Vec<Token>),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Token {
+    Char(char),
+    AnyChar,
+    AnySequence,
+    /// Whether the class is negated, and its inclusive ranges
+    Class(bool, Vec<(char, char)>),
+}
+
+impl Pattern {
+    /// Compiles a glob pattern.
+    ///
+    /// Returns an error if a `[` or `{` is not closed, or a `}` is not opened.
+    pub fn new(pattern: &str) -> Result<Pattern, PatternError> {
+        let mut alternatives = Vec::new();
+        for alternative in try!(expand_braces(pattern)) {
+            let (root, comps) = split_root(Path::new(&alternative));
+            let mut segments = Vec::new();
+            for comp in comps {
+                let name = comp.as_os_str().to_string_lossy();
+                segments.push(match comp {
+                    Component::Normal(_) if name == "**" => Segment::AnyDirs,
+                    Component::Normal(_) => Segment::Name(try!(parse_tokens(&name))),
+                    _ => Segment::Exact(name.into_owned()),
+                });
+            }
+            alternatives.push(Alternative {
+                root: root,
+                segments: segments,
+            });
+        }
+        Ok(Pattern {
+            original: pattern.to_owned(),
+            alternatives: alternatives,
+        })
+    }
+
+    /// The pattern as it was written.
+    pub fn as_str(&self) -> &str {
+        &self.original
+    }
+
+    /// The directories the matches of the pattern are found in, each with
+    /// the largest number of components a match can have below it, or `None`
+    /// if the pattern has a `**` there.
+    ///
+    /// The base of each `{a,b}` alternative is its prefix, root and leading
+    /// components without special characters, which is empty for a relative
+    /// alternative without such components. A base inside another one is
+    /// merged into it, so every path is below at most one base.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::path::{PathBuf, Pattern};
+    ///
+    /// assert_eq!(Pattern::new("src/**/*.rs").unwrap().bases(),
+    ///            vec![(PathBuf::from("src"), None)]);
+    /// assert_eq!(Pattern::new("{/usr,/etc}/*").unwrap().bases(),
+    ///            vec![(PathBuf::from("/usr"), Some(1)), (PathBuf::from("/etc"), Some(1))]);
+    /// ```
+    pub fn bases(&self) -> Vec<(PathBuf, Option<usize>)> {
+        // Each base is kept with its root, as an empty relative base would
+        // otherwise seem to contain every absolute one
+        let mut bases: Vec<(&str, PathBuf, Option<usize>)> = Vec::new();
+        for alternative in &self.alternatives {
+            let mut base = alternative.root.clone();
+            let mut literal = 0;
+            for segment in &alternative.segments {
+                match literal_name(segment) {
+                    Some(name) => {
+                        if literal > 0 {
+                            base.push_str(MAIN_SEP_STR);
+                        }
+                        base.push_str(&name);
+                        literal += 1;
+                    },
+                    None => break,
+                }
+            }
+            let root = &alternative.root[..];
+            let base = PathBuf::from(base);
+            let rest = &alternative.segments[literal..];
+            let mut depth = if rest.contains(&Segment::AnyDirs) {
+                None
+            } else {
+                Some(rest.len())
+            };
+
+            // Bases never nest, so this one is either inside a single base,
+            // or contains any number of them
+            let mut outer = None;
+            let mut i = 0;
+            while i < bases.len() {
+                if bases[i].0 != root {
+                    i += 1;
+                } else if base.starts_with(&bases[i].1) {
+                    outer = Some(i);
+                    break;
+                } else if bases[i].1.starts_with(&base) {
+                    let (_, inner, inner_depth) = bases.remove(i);
+                    depth = deeper(depth, inner_depth, &inner, &base);
+                } else {
+                    i += 1;
+                }
+            }
+            match outer {
+                Some(i) => {
+                    let depth = deeper(bases[i].2, depth, &base, &bases[i].1);
+                    bases[i].2 = depth;
+                },
+                None => bases.push((root, base, depth)),
+            }
+        }
+        bases.into_iter().map(|(_, base, depth)| (base, depth)).collect()
+    }
+
+    /// Determines whether `path` matches the pattern.
+    ///
+    /// The path is matched as written, without accessing the file system.
+    pub fn matches<P: AsRef<Path>>(&self, path: P) -> bool {
+        let (root, comps) = split_root(path.as_ref());
+        self.alternatives.iter().any(|alternative| {
+            alternative.root == root && match_segments(&alternative.segments, &comps)
+        })
+    }
+}
+
+impl fmt::Display for Pattern {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.original.fmt(f)
+    }
+}
+
+/// An error returned from `Pattern::new` for a malformed pattern.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PatternError {
+    msg: &'static str,
+}
+
+impl fmt::Display for PatternError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.description().fmt(f)
+    }
+}
+
+impl Error for PatternError {
+    fn description(&self) -> &str { self.msg }
+}
+
+// Splits a path into its prefix and root, as written, and its other components.
+fn split_root(path: &Path) -> (String, Vec<Component>) {
+    let comps = path.components();
+    let has_physical_root = comps.has_physical_root;
+    let mut root = String::new();
+    let mut rest = Vec::new();
+    for comp in comps {
+        match comp {
+            Component::Prefix(_) => root.push_str(&comp.as_os_str().to_string_lossy()),
+            Component::RootDir => if has_physical_root {
+                root.push_str(MAIN_SEP_STR);
+            },
+            _ => rest.push(comp),
+        }
+    }
+    (root, rest)
+}
+
+// The name a segment matches, if it has no special characters.
+fn literal_name(segment: &Segment) -> Option<String> {
+    match *segment {
+        Segment::Exact(ref name) => Some(name.clone()),
+        Segment::AnyDirs => None,
+        Segment::Name(ref tokens) => {
+            let mut name = String::new();
+            for token in tokens {
+                match *token {
+                    Token::Char(c) => name.push(c),
+                    _ => return None,
+                }
+            }
+            Some(name)
+        }
+    }
+}
+
+// Combines the depth below `outer` with the depth below `inner`, a base
+// inside it, into the depth of either below `outer`.
+fn deeper(depth: Option<usize>, inner_depth: Option<usize>, inner: &Path, outer: &Path) -> Option<usize> {
+    let below = inner.components().count() - outer.components().count();
+    match (depth, inner_depth) {
+        (Some(depth), Some(inner_depth)) => Some(cmp::max(depth, inner_depth + below)),
+        _ => None,
+    }
+}
+
+// Finds the `]` closing the character class opened at `start`.
+fn class_end(pattern: &[u8], start: usize) -> Option<usize> {
+    let mut i = start + 1;
+    if i < pattern.len() && (pattern[i] == b'!' || pattern[i] == b'^') {
+        i += 1;
+    }
+    // A `]` right at the start is part of the class
+    if i < pattern.len() && pattern[i] == b']' {
+        i += 1;
+    }
+    pattern[i..].iter().position(|&b| b == b']').map(|pos| i + pos)
+}
+
+// Expands the first `{a,b}` group of `pattern`, recursively, into every
+// pattern it stands for.
+fn expand_braces(pattern: &str) -> Result<Vec<String>, PatternError> {
+    // Only ASCII bytes are looked at, so slicing at them is safe
+    let bytes = pattern.as_bytes();
+    let mut start = 0;
+    let mut depth = 0;
+    let mut commas = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'[' => match class_end(bytes, i) {
+                Some(end) => i = end,
+                None => return Err(PatternError { msg: "unterminated character class" }),
+            },
+            b'{' => {
+                if depth == 0 {
+                    start = i;
+                }
+                depth += 1;
+            },
+            b',' if depth == 1 => commas.push(i),
+            b'}' if depth == 0 => return Err(PatternError { msg: "unmatched `}`" }),
+            b'}' => {
+                depth -= 1;
+                if depth == 0 {
+                    let mut bounds = vec![start];
+                    bounds.extend(commas);
+                    bounds.push(i);
+
+                    let mut expanded = Vec::new();
+                    for option in bounds.windows(2) {
+                        let mut alternative = String::new();
+                        alternative.push_str(&pattern[..start]);
+                        alternative.push_str(&pattern[option[0] + 1..option[1]]);
+                        alternative.push_str(&pattern[i + 1..]);
+                        expanded.extend(try!(expand_braces(&alternative)));
+                    }
+                    return Ok(expanded);
+                }
+            },
+            _ => (),
+        }
+        i += 1;
+    }
+
+    if depth > 0 {
+        Err(PatternError { msg: "unterminated `{`" })
+    } else {
+        Ok(vec![pattern.to_owned()])
+    }
+}
+
+// Compiles a single component of a pattern.
+fn parse_tokens(name: &str) -> Result<Vec<Token>, PatternError> {
+    let chars: Vec<char> = name.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        match chars[i] {
+            '?' => tokens.push(Token::AnyChar),
+            '*' => if tokens.last() != Some(&Token::AnySequence) {
+                tokens.push(Token::AnySequence);
+            },
+            '[' => {
+                i += 1;
+                let negated = i < chars.len() && (chars[i] == '!' || chars[i] == '^');
+                if negated {
+                    i += 1;
+                }
+                let mut ranges = Vec::new();
+                loop {
+                    if i >= chars.len() {
+                        return Err(PatternError { msg: "unterminated character class" });
+                    }
+                    if chars[i] == ']' && !ranges.is_empty() {
+                        break;
+                    }
+                    if i + 2 < chars.len() && chars[i + 1] == '-' && chars[i + 2] != ']' {
+                        ranges.push((chars[i], chars[i + 2]));
+                        i += 3;
+                    } else {
+                        ranges.push((chars[i], chars[i]));
+                        i += 1;
+                    }
+                }
+                tokens.push(Token::Class(negated, ranges));
+            },
+            c => tokens.push(Token::Char(c)),
+        }
+        i += 1;
+    }
+    Ok(tokens)
+}
+
+// Matches a component against its tokens, backtracking only to the last `*`,
+// so the time is bounded by the product of their lengths.
+fn match_tokens(tokens: &[Token], name: &[char]) -> bool {
+    let (mut t, mut n) = (0, 0);
+    // The token after the last `*` seen, and the position in `name` it was tried at
+    let mut star: Option<(usize, usize)> = None;
+    while n < name.len() {
+        let matched = match tokens.get(t) {
+            Some(&Token::AnySequence) => {
+                star = Some((t + 1, n));
+                t += 1;
+                continue;
+            },
+            Some(&Token::Char(c)) => c == name[n],
+            Some(&Token::AnyChar) => true,
+            Some(&Token::Class(negated, ref ranges)) => {
+                ranges.iter().any(|&(lo, hi)| lo <= name[n] && name[n] <= hi) != negated
+            },
+            None => false,
+        };
+        if matched {
+            t += 1;
+            n += 1;
+        } else if let Some((star_t, star_n)) = star {
+            // Let the last `*` take one more character, and try again after it
+            t = star_t;
+            n = star_n + 1;
+            star = Some((star_t, star_n + 1));
+        } else {
+            return false;
+        }
+    }
+    tokens[t..].iter().all(|token| *token == Token::AnySequence)
+}
+
+fn is_hidden(name: &OsStr) -> bool {
+    name.to_string_lossy().starts_with('.')
+}
+
+fn match_segments(segments: &[Segment], comps: &[Component]) -> bool {
+    match segments.split_first() {
+        None => comps.is_empty(),
+        Some((&Segment::AnyDirs, rest)) => {
+            let mut i = 0;
+            loop {
+                if match_segments(rest, &comps[i..]) {
+                    return true;
+                }
+                match comps.get(i) {
+                    Some(&Component::Normal(name)) if !is_hidden(name) => i += 1,
+                    _ => return false,
+                }
+            }
+        },
+        Some((segment, rest)) => match comps.split_first() {
+            Some((&comp, comps)) => {
+                let matched = match (segment, comp) {
+                    (&Segment::Exact(ref exact), _) => comp.as_os_str() == OsStr::new(exact),
+                    (&Segment::Name(ref tokens), Component::Normal(name)) => {
+                        let literal_dot = tokens.first() == Some(&Token::Char('.'));
+                        let chars: Vec<char> = name.to_string_lossy().chars().collect();
+                        (literal_dot || !is_hidden(name)) && match_tokens(tokens, &chars)
+                    },
+                    _ => false,
+                };
+                matched && match_segments(rest, comps)
+            },
+            None => false,
+        },
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -2545,6 +3158,95 @@ mod tests {
            );
     }
 
+    #[test]
+    #[cfg(redox)]
+    pub fn test_decompositions_redox() {
+        t!("file:/home/user",
+           iter: ["file:", "/", "home", "user"],
+           has_root: true,
+           is_absolute: true,
+           parent: Some("file:/home"),
+           file_name: Some("user"),
+           file_stem: Some("user"),
+           extension: None
+           );
+
+        t!("file:/",
+           iter: ["file:", "/"],
+           has_root: true,
+           is_absolute: true,
+           parent: None,
+           file_name: None,
+           file_stem: None,
+           extension: None
+           );
+
+        t!("tcp:127.0.0.1:80",
+           iter: ["tcp:", "/", "127.0.0.1:80"],
+           has_root: true,
+           is_absolute: true,
+           parent: Some("tcp:"),
+           file_name: Some("127.0.0.1:80"),
+           file_stem: Some("127.0.0"),
+           extension: Some("1:80")
+           );
+
+        t!("tcp:",
+           iter: ["tcp:", "/"],
+           has_root: true,
+           is_absolute: true,
+           parent: None,
+           file_name: None,
+           file_stem: None,
+           extension: None
+           );
+
+        t!("foo/bar:baz",
+           iter: ["foo", "bar:baz"],
+           has_root: false,
+           is_absolute: false,
+           parent: Some("foo"),
+           file_name: Some("bar:baz"),
+           file_stem: Some("bar:baz"),
+           extension: None
+           );
+
+        t!("/foo:bar",
+           iter: ["/", "foo:bar"],
+           has_root: true,
+           is_absolute: true,
+           parent: Some("/"),
+           file_name: Some("foo:bar"),
+           file_stem: Some("foo:bar"),
+           extension: None
+           );
+
+        t!(":foo",
+           iter: [":foo"],
+           has_root: false,
+           is_absolute: false,
+           parent: Some(""),
+           file_name: Some(":foo"),
+           file_stem: Some(":foo"),
+           extension: None
+           );
+    }
+
+    #[test]
+    #[cfg(redox)]
+    pub fn test_scheme() {
+        use ffi::OsStr;
+
+        assert_eq!(Path::new("file:/home/user").scheme(), Some(OsStr::new("file")));
+        assert_eq!(Path::new("tcp:127.0.0.1:80").scheme(), Some(OsStr::new("tcp")));
+        assert_eq!(Path::new("tcp:").scheme(), Some(OsStr::new("tcp")));
+        assert_eq!(Path::new("/home/user").scheme(), None);
+        assert_eq!(Path::new("home/user:1").scheme(), None);
+        assert_eq!(Path::new(":foo").scheme(), None);
+
+        assert_eq!(Path::new("file:/home").prefix(), Some(Prefix::Scheme(OsStr::new("file"))));
+    }
+
     #[test]
     #[cfg(windows)]
     pub fn test_decompositions_windows() {
@@ -3147,7 +3849,7 @@ mod tests {
             });
         );
 
-        if cfg!(unix) {
+        if cfg!(unix) || cfg!(redox) {
             tp!("", "foo", "foo");
             tp!("foo", "bar", "foo/bar");
             tp!("foo/", "bar", "foo/bar");
@@ -3161,6 +3863,15 @@ mod tests {
             tp!("/foo/bar", "/", "/");
             tp!("/foo/bar", "/baz", "/baz");
             tp!("/foo/bar", "./baz", "/foo/bar/./baz");
+
+            if cfg!(redox) {
+                tp!("file:/home", "user", "file:/home/user");
+                tp!("file:/", "home", "file:/home");
+                tp!("tcp:", "127.0.0.1:80", "tcp:127.0.0.1:80");
+                tp!("file:/home", "/user", "/user");
+                tp!("file:/home", "tcp:127.0.0.1:80", "tcp:127.0.0.1:80");
+                tp!("/home", "file:/user", "file:/user");
+            }
         } else {
             tp!("", "foo", "foo");
             tp!("foo", "bar", r"foo\bar");
@@ -3287,6 +3998,200 @@ mod tests {
 
             tp!("\\\\?\\a\\b\\", "\\\\?\\a\\", true);
         }
+
+        if cfg!(redox) {
+            tp!("file:/home/user", "file:/home", true);
+            tp!("file:/home", "file:/", true);
+            tp!("file:/", "file:/", false);
+            tp!("tcp:127.0.0.1:80", "tcp:", true);
+            tp!("tcp:", "tcp:", false);
+        }
+    }
+
+    #[test]
+    pub fn test_normalize() {
+        macro_rules! tn(
+            ($path:expr, $expected:expr) => ( {
+                let actual = Path::new($path).normalize();
+                assert!(actual.to_str() == Some($expected),
+                        "normalizing {:?}: Expected {:?}, got {:?}",
+                        $path, $expected, actual.to_str().unwrap());
+            });
+        );
+
+        tn!("", ".");
+        tn!(".", ".");
+        tn!("./", ".");
+        tn!("/", "/");
+        tn!("foo", "foo");
+        tn!("foo/", "foo");
+        tn!("./foo", "foo");
+        tn!("foo/./bar", "foo/bar");
+        tn!("foo//bar", "foo/bar");
+        tn!("foo/..", ".");
+        tn!("foo/../bar", "bar");
+        tn!("foo/bar/../../baz", "baz");
+        tn!("..", "..");
+        tn!("../..", "../..");
+        tn!("../foo/../bar", "../bar");
+        tn!("foo/../../bar", "../bar");
+        tn!("/..", "/");
+        tn!("/../foo", "/foo");
+        tn!("/foo/../..", "/");
+        tn!("/usr/./lib/../bin/", "/usr/bin");
+
+        if cfg!(redox) {
+            tn!("file:/home/../etc", "file:/etc");
+            tn!("file:/..", "file:/");
+            tn!("tcp:", "tcp:");
+            tn!("tcp:foo/../bar", "tcp:bar");
+        }
+    }
+
+    #[test]
+    pub fn test_relative_to() {
+        macro_rules! tr(
+            ($path:expr, $base:expr, $expected:expr) => ( {
+                let actual = Path::new($path).relative_to($base);
+                let actual = actual.as_ref().map(|p| p.to_str().unwrap());
+                let expected: Option<&str> = $expected;
+                assert!(actual == expected,
+                        "{:?} relative to {:?}: Expected {:?}, got {:?}",
+                        $path, $base, expected, actual);
+            });
+        );
+
+        tr!("/usr/lib", "/usr/lib", Some("."));
+        tr!("/usr/lib/libc.a", "/usr/lib", Some("libc.a"));
+        tr!("/usr/lib", "/usr/lib/rustlib", Some(".."));
+        tr!("/usr", "/usr/lib/rustlib", Some("../.."));
+        tr!("/usr/lib/libc.a", "/usr/bin", Some("../lib/libc.a"));
+        tr!("/usr/lib", "/etc/ssl/certs", Some("../../../usr/lib"));
+        tr!("/", "/usr", Some(".."));
+        tr!("/usr", "/", Some("usr"));
+        tr!("/usr/./lib/../bin", "/usr/lib/..", Some("bin"));
+        tr!("foo/bar", "foo/baz", Some("../bar"));
+        tr!("foo", "", Some("foo"));
+        tr!("", "foo", Some(".."));
+        tr!("../foo", "bar", Some("../../foo"));
+        tr!("../foo", "../bar", Some("../foo"));
+        tr!("foo", "../bar", None);
+        tr!("/usr/lib", "lib", None);
+        tr!("lib", "/usr/lib", None);
+
+        if cfg!(redox) {
+            tr!("file:/usr/lib", "file:/usr/bin", Some("../lib"));
+            tr!("file:/usr/lib", "tcp:/usr/bin", None);
+            tr!("file:/usr/lib", "/usr/bin", None);
+        }
+    }
+
+    #[test]
+    pub fn test_pattern() {
+        macro_rules! tm(
+            ($pattern:expr, $path:expr, $expected:expr) => ( {
+                let pattern = Pattern::new($pattern).unwrap();
+                assert!(pattern.matches($path) == $expected,
+                        "matching {:?} against {:?}: Expected {:?}",
+                        $path, $pattern, $expected);
+            });
+        );
+
+        tm!("foo", "foo", true);
+        tm!("foo", "foo/", true);
+        tm!("foo", "bar", false);
+        tm!("foo", "foo/bar", false);
+        tm!("*", "foo", true);
+        tm!("*", "", false);
+        tm!("*", "foo/bar", false);
+        tm!("*.rs", "lib.rs", true);
+        tm!("*.rs", "lib.rs.bk", false);
+        tm!("*.rs", ".rs", false);
+        tm!("*", ".hidden", false);
+        tm!(".*", ".hidden", true);
+        tm!("a*b*c", "abc", true);
+        tm!("a*b*c", "axxbyyc", true);
+        tm!("a*b*c", "axxbyy", false);
+        tm!("a*a*a*a*a*a*a*a*b", "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa", false);
+        tm!("*a?", "abab", true);
+        tm!("*ab", "aab", true);
+        tm!("?", "a", true);
+        tm!("?", "ab", false);
+        tm!("f?o", "foo", true);
+        tm!("[abc]", "b", true);
+        tm!("[abc]", "d", false);
+        tm!("[a-z]", "q", true);
+        tm!("[a-z]", "Q", false);
+        tm!("[!a-z]", "Q", true);
+        tm!("[^a-z]", "q", false);
+        tm!("[]]", "]", true);
+        tm!("[a-]", "-", true);
+        tm!("[*]", "*", true);
+        tm!("[*]", "a", false);
+        tm!("{foo,bar}", "foo", true);
+        tm!("{foo,bar}", "bar", true);
+        tm!("{foo,bar}", "baz", false);
+        tm!("a{,b}", "a", true);
+        tm!("a{,b}", "ab", true);
+        tm!("{a,b{c,d}}", "bd", true);
+        tm!("{src/*.rs,Cargo.toml}", "src/lib.rs", true);
+        tm!("{src/*.rs,Cargo.toml}", "Cargo.toml", true);
+        tm!("**", "", true);
+        tm!("**", "a/b/c", true);
+        tm!("**/*.rs", "lib.rs", true);
+        tm!("**/*.rs", "src/sys/mod.rs", true);
+        tm!("**/*.rs", ".git/hooks/x.rs", false);
+        tm!("src/**/mod.rs", "src/mod.rs", true);
+        tm!("src/**/mod.rs", "src/a/b/mod.rs", true);
+        tm!("src/**/mod.rs", "lib/a/mod.rs", false);
+        tm!("src/*/mod.rs", "src/a/b/mod.rs", false);
+        tm!("/usr/*", "/usr/bin", true);
+        tm!("/usr/*", "usr/bin", false);
+        tm!("usr/*", "/usr/bin", false);
+        tm!("*", "/", false);
+        tm!("./*", "./foo", true);
+        tm!("../*", "../foo", true);
+        tm!("*", "..", false);
+
+        if cfg!(redox) {
+            tm!("file:/home/*", "file:/home/user", true);
+            tm!("file:/home/*", "/home/user", false);
+            tm!("tcp:*", "tcp:127.0.0.1:80", true);
+            tm!("tcp:*", "udp:127.0.0.1:80", false);
+        }
+
+        assert_eq!(Pattern::new("src/*.rs").unwrap().as_str(), "src/*.rs");
+
+        macro_rules! tb(
+            ($pattern:expr, $expected:expr) => ( {
+                let expected: Vec<(PathBuf, Option<usize>)> = $expected.iter()
+                    .map(|&(base, depth)| (PathBuf::from(base), depth)).collect();
+                assert!(Pattern::new($pattern).unwrap().bases() == expected,
+                        "bases of {:?}: expected {:?}", $pattern, expected);
+            });
+        );
+
+        tb!("src/*.rs", [("src", Some(1))]);
+        tb!("src/lib.rs", [("src/lib.rs", Some(0))]);
+        tb!("*/lib.rs", [("", Some(2))]);
+        tb!("src/**/*.rs", [("src", None)]);
+        tb!("/usr/*", [("/usr", Some(1))]);
+        tb!("{/usr,/etc}/*", [("/usr", Some(1)), ("/etc", Some(1))]);
+        tb!("{/usr/*,*}", [("/usr", Some(1)), ("", Some(1))]);
+        tb!("src/{a,b/*/c}", [("src/a", Some(0)), ("src/b", Some(2))]);
+        tb!("{a/b/*,a/*}", [("a", Some(2))]);
+        tb!("{a/*,a/b/**}", [("a", None)]);
+        tb!("{a,b}/[*]", [("a", Some(1)), ("b", Some(1))]);
+
+        if cfg!(redox) {
+            tb!("tcp:*", [("tcp:", Some(1))]);
+        }
+
+        assert!(Pattern::new("[abc").is_err());
+        assert!(Pattern::new("[]").is_err());
+        assert!(Pattern::new("{a,b").is_err());
+        assert!(Pattern::new("a,b}").is_err());
+        assert!(Pattern::new("[{]").is_ok());
     }
 
     #[test]
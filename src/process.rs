@@ -1,15 +1,20 @@
 use borrow::ToOwned;
+use cmp;
 use collections::BTreeMap;
 use env;
 use fmt;
-use io::{Result, Read, Write};
+use fs::File;
+use io::{IoSlice, IoSliceMut, Result, Read, Write};
 use os::unix::io::{AsRawFd, FromRawFd, RawFd};
 use path::{Path, PathBuf};
 use string::{String, ToString};
+use sys_common::io as sys_io;
+use thread;
+use time::Duration;
 use vec::Vec;
 
 use io::Error;
-use syscall::{self, clone, close, dup, execve, pipe2, read, write, waitpid, CLONE_VFORK, CLONE_SUPERVISE};
+use syscall::{self, clone, close, dup, execve, pipe2, read, write, waitpid, CLONE_VFORK, CLONE_SUPERVISE, Packet};
 use syscall::Error as SysError;
 
 pub struct ExitStatus {
@@ -35,6 +40,10 @@ impl Write for ChildStdin {
         write(self.fd, buf).map_err(|x| Error::from_sys(x))
     }
     fn flush(&mut self) -> Result<()> { Ok(()) }
+
+    fn write_vectored(&mut self, bufs: &[IoSlice]) -> Result<usize> {
+        sys_io::write_vectored(self.fd, bufs)
+    }
 }
 
 impl Drop for ChildStdin {
@@ -57,6 +66,10 @@ impl Read for ChildStdout {
     fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
         read(self.fd, buf).map_err(|x| Error::from_sys(x))
     }
+
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut]) -> Result<usize> {
+        sys_io::read_vectored(self.fd, bufs)
+    }
 }
 
 impl Drop for ChildStdout {
@@ -73,6 +86,10 @@ impl Read for ChildStderr {
     fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
         read(self.fd, buf).map_err(|x| Error::from_sys(x))
     }
+
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut]) -> Result<usize> {
+        sys_io::read_vectored(self.fd, bufs)
+    }
 }
 
 impl Drop for ChildStderr {
@@ -97,6 +114,264 @@ impl Child {
         let mut status: usize = 0;
         waitpid(self.pid, &mut status, 0).map(|_| ExitStatus { status: status }).map_err(|x| Error::from_sys(x))
     }
+
+    /// Wait for the child to exit, sampling its resource usage while it runs.
+    ///
+    /// Redox keeps no usage totals for a reaped child, so `sys:context` is sampled until the child
+    /// exits, first right away and then at intervals growing from 1 ms to 10 ms. The result is a
+    /// best-effort lower bound: usage after the last sample is missed, and a child that exits
+    /// before the first sample reports none, which `ResourceUsage::samples` shows.
+    pub fn wait_with_rusage(&mut self) -> Result<(ExitStatus, ResourceUsage)> {
+        let mut usage = ResourceUsage {
+            cpu_time: Duration::new(0, 0),
+            peak_memory: 0,
+            samples: 0,
+        };
+
+        let mut interval = 1;
+        loop {
+            if let Ok(Some((cpu_time, memory))) = context_usage(self.pid) {
+                usage.samples += 1;
+                if cpu_time > usage.cpu_time {
+                    usage.cpu_time = cpu_time;
+                }
+                if memory > usage.peak_memory {
+                    usage.peak_memory = memory;
+                }
+            }
+
+            let mut status: usize = 0;
+            let pid = try!(waitpid(self.pid, &mut status, syscall::flag::WNOHANG).map_err(|x| Error::from_sys(x)));
+            if pid == self.pid {
+                return Ok((ExitStatus { status: status }, usage));
+            }
+
+            thread::sleep(Duration::from_millis(interval));
+            if interval < 10 {
+                interval = cmp::min(interval * 2, 10);
+            }
+        }
+    }
+}
+
+/// Resources used by a child, as sampled by `Child::wait_with_rusage`
+///
+/// The values are the largest ones seen in any sample, so they can be lower than the real usage.
+#[derive(Copy, Clone, Debug)]
+pub struct ResourceUsage {
+    cpu_time: Duration,
+    peak_memory: u64,
+    samples: usize,
+}
+
+impl ResourceUsage {
+    /// The CPU time the child was last seen to have used
+    ///
+    /// Redox does not account user and system time separately, so this is their sum.
+    pub fn cpu_time(&self) -> Duration {
+        self.cpu_time
+    }
+
+    /// The largest amount of memory, in bytes, seen in use by the child
+    pub fn peak_memory(&self) -> u64 {
+        self.peak_memory
+    }
+
+    /// The number of times the child was sampled, where zero means nothing is known about it
+    pub fn samples(&self) -> usize {
+        self.samples
+    }
+}
+
+/// Cut the column starting at `start` and ending before `end` out of a line
+fn context_column(line: &str, start: usize, end: Option<usize>) -> &str {
+    let end = cmp::min(end.unwrap_or(line.len()), line.len());
+    if start < end && line.is_char_boundary(start) && line.is_char_boundary(end) {
+        line[start..end].trim()
+    } else {
+        ""
+    }
+}
+
+/// Look up the CPU time and memory of a context in `sys:context`
+fn context_usage(pid: usize) -> Result<Option<(Duration, u64)>> {
+    let mut string = String::new();
+    try!(try!(File::open("sys:context")).read_to_string(&mut string));
+    Ok(parse_context_usage(&string, pid))
+}
+
+/// Find the CPU time and memory of a context in the contents of `sys:context`
+fn parse_context_usage(string: &str, pid: usize) -> Option<(Duration, u64)> {
+    let mut lines = string.lines();
+    let header = match lines.next() {
+        Some(line) => line,
+        None => return None
+    };
+
+    // Values are left aligned under their headings and can contain spaces, so each column is
+    // cut out from the start of its heading to the start of the next one
+    let mut headings = Vec::new();
+    let mut in_heading = false;
+    for (i, c) in header.char_indices() {
+        if c.is_whitespace() {
+            in_heading = false;
+        } else if ! in_heading {
+            in_heading = true;
+            headings.push(i);
+        }
+    }
+    let column = |name: &str| {
+        headings.iter().position(|&start| header[start..].split_whitespace().next() == Some(name))
+                .map(|i| (headings[i], headings.get(i + 1).cloned()))
+    };
+    let pid_col = match column("PID") {
+        Some(col) => col,
+        None => return None
+    };
+    let time_col = column("TIME");
+    let mem_col = column("MEM");
+
+    for line in lines {
+        if context_column(line, pid_col.0, pid_col.1).parse::<usize>().ok() != Some(pid) {
+            continue;
+        }
+
+        let cpu_time = time_col.and_then(|(start, end)| parse_context_time(context_column(line, start, end)))
+                               .unwrap_or(Duration::new(0, 0));
+        let memory = mem_col.and_then(|(start, end)| parse_context_memory(context_column(line, start, end)))
+                            .unwrap_or(0);
+
+        return Some((cpu_time, memory));
+    }
+
+    None
+}
+
+/// Parse an amount of memory from `sys:context`, which is a number followed by a unit, such as
+/// `12 KB`
+fn parse_context_memory(string: &str) -> Option<u64> {
+    let mut parts = string.split_whitespace();
+    let count = parts.next().and_then(|count| count.parse::<u64>().ok());
+    let unit = match parts.next() {
+        Some("KB") => 1 << 10,
+        Some("MB") => 1 << 20,
+        Some("GB") => 1 << 30,
+        _ => 1
+    };
+    count.map(|count| count * unit)
+}
+
+/// Parse a `hh:mm:ss.cc` time from `sys:context`
+fn parse_context_time(string: &str) -> Option<Duration> {
+    let mut parts = string.split(|c: char| c == ':' || c == '.').map(|part| part.parse::<u64>().ok());
+    match (parts.next(), parts.next(), parts.next(), parts.next()) {
+        (Some(Some(hours)), Some(Some(minutes)), Some(Some(secs)), centis) => {
+            let centis = centis.and_then(|centis| centis).unwrap_or(0);
+            Some(Duration::new(hours * 3600 + minutes * 60 + secs, centis as u32 * 10_000_000))
+        },
+        _ => None
+    }
+}
+
+/// A supervisor for a child spawned with `Command::spawn_supervise`
+pub struct Supervisor {
+    fd: usize,
+}
+
+impl Supervisor {
+    /// Start supervising a child
+    pub fn new(child: &Child) -> Result<Supervisor> {
+        syscall::supervise(child.pid).map(|fd| Supervisor { fd: fd }).map_err(|x| Error::from_sys(x))
+    }
+
+    /// Block until the child makes a system call, returning `None` once the child has exited
+    pub fn next_call(&mut self) -> Result<Option<SupervisedCall>> {
+        let mut packet = Packet::default();
+        match read(self.fd, &mut packet) {
+            Ok(0) => Ok(None),
+            Ok(_) => Ok(Some(SupervisedCall {
+                supervisor: self,
+                packet: packet,
+                answered: false,
+            })),
+            Err(err) => Err(Error::from_sys(err))
+        }
+    }
+}
+
+impl AsRawFd for Supervisor {
+    fn as_raw_fd(&self) -> RawFd {
+        self.fd
+    }
+}
+
+impl Drop for Supervisor {
+    fn drop(&mut self) {
+        let _ = close(self.fd);
+    }
+}
+
+/// A system call intercepted from a supervised child
+///
+/// The child stays blocked until the call is answered with `resume`, `deny` or `set_return`. A
+/// call that is dropped without an answer is resumed, so the child is never left blocked.
+pub struct SupervisedCall<'a> {
+    supervisor: &'a Supervisor,
+    packet: Packet,
+    answered: bool,
+}
+
+impl<'a> SupervisedCall<'a> {
+    /// The system call number
+    pub fn number(&self) -> usize {
+        self.packet.a
+    }
+
+    /// The arguments of the system call
+    pub fn args(&self) -> [usize; 3] {
+        [self.packet.b, self.packet.c, self.packet.d]
+    }
+
+    /// Let the kernel perform the system call unchanged
+    pub fn resume(mut self) -> Result<()> {
+        self.answer_resume()
+    }
+
+    /// Fail the system call with the given error number, without performing it
+    pub fn deny(self, errno: i32) -> Result<()> {
+        self.set_return(SysError::mux(Err(SysError::new(errno))))
+    }
+
+    /// Skip the system call, returning `value` to the child instead
+    pub fn set_return(mut self, value: usize) -> Result<()> {
+        self.answered = true;
+        // Writing a single word answers the call with that word as its result
+        let buf: [u8; 8] = unsafe { ::mem::transmute(value as u64) };
+        write(self.supervisor.fd, &buf).and(Ok(())).map_err(|x| Error::from_sys(x))
+    }
+
+    fn answer_resume(&mut self) -> Result<()> {
+        self.answered = true;
+        // Writing the packet back hands the call to the kernel as is
+        write(self.supervisor.fd, &self.packet).and(Ok(())).map_err(|x| Error::from_sys(x))
+    }
+}
+
+impl<'a> Drop for SupervisedCall<'a> {
+    fn drop(&mut self) {
+        if ! self.answered {
+            let _ = self.answer_resume();
+        }
+    }
+}
+
+impl<'a> fmt::Debug for SupervisedCall<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("SupervisedCall")
+            .field("number", &self.number())
+            .field("args", &self.args())
+            .finish()
+    }
 }
 
 pub struct Command {
@@ -104,6 +379,7 @@ pub struct Command {
     args: Vec<String>,
     uid: Option<u32>,
     gid: Option<u32>,
+    schemes: Option<Vec<String>>,
     dir: Option<PathBuf>,
     env: BTreeMap<String, String>,
     stdin: Stdio,
@@ -136,6 +412,13 @@ impl ::os::unix::process::CommandExt for Command {
     }
 }
 
+impl ::os::redox::process::CommandExt for Command {
+    fn schemes(&mut self, schemes: &[&str]) -> &mut Command {
+        self.schemes = Some(schemes.iter().map(|scheme| scheme.to_string()).collect());
+        self
+    }
+}
+
 impl Command {
     pub fn new(path: &str) -> Command {
         Command {
@@ -143,6 +426,7 @@ impl Command {
             args: Vec::new(),
             uid: None,
             gid: None,
+            schemes: None,
             dir: None,
             env: BTreeMap::new(),
             stdin: Stdio::inherit(),
@@ -188,8 +472,8 @@ impl Command {
     /// Spawn this command as a supervised process.
     ///
     /// This means that the system calls will block the process, until being handled by the
-    /// parrent. Handling can be done by calling `.id()`, and then using `sys_supervise` to start
-    /// supervising this process. Refer to the respective documentation for more information.
+    /// parrent. Handling can be done by passing the child to `Supervisor::new`, which yields
+    /// every intercepted system call as a `SupervisedCall`.
     pub fn spawn_supervise(&mut self) -> Result<Child> {
         self.exec(CLONE_SUPERVISE)
     }
@@ -218,6 +502,8 @@ impl Command {
         let child_uid = self.uid.clone();
         let child_gid = self.gid.clone();
 
+        let child_schemes = self.schemes.clone();
+
         let child_dir = self.dir.clone();
 
         let child_env = self.env.clone();
@@ -312,6 +598,16 @@ impl Command {
                         env::set_var(key, val);
                     }
 
+                    // Entering the namespace comes last, as the steps above may need other schemes
+                    if let Some(ref schemes) = child_schemes {
+                        let mut names: Vec<[usize; 2]> = Vec::new();
+                        for scheme in schemes.iter() {
+                            names.push([scheme.as_ptr() as usize, scheme.len()]);
+                        }
+                        let ns = try!(syscall::mkns(&names).map_err(|x| Error::from_sys(x)));
+                        try!(syscall::setrens(ns, ns).map_err(|x| Error::from_sys(x)));
+                    }
+
                     execve(&path, &args).map_err(|x| Error::from_sys(x))
                 };
 
@@ -458,3 +754,67 @@ pub fn exit(code: i32) -> ! {
         let _ = syscall::exit(code as usize);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use prelude::v1::*;
+    use time::Duration;
+    use super::{context_column, parse_context_memory, parse_context_time, parse_context_usage};
+
+    const CONTEXTS: &'static str = "\
+PID   PPID  STAT  CPU   TIME        MEM     NAME
+1     0     UB    0     00:00:01.50 12 KB   init
+12    1     R+    1     01:02:03.04 3 MB    file:/bin/ion -c true
+345   12    B     0     00:00:00.00 0 B     tcp:
+";
+
+    #[test]
+    fn context_columns() {
+        let line = "345   12    B     0     00:00:00.00 0 B     tcp:";
+        assert_eq!(context_column(line, 0, Some(6)), "345");
+        assert_eq!(context_column(line, 36, Some(44)), "0 B");
+        assert_eq!(context_column(line, 44, None), "tcp:");
+        assert_eq!(context_column(line, 44, Some(100)), "tcp:");
+        assert_eq!(context_column(line, 60, None), "");
+        assert_eq!(context_column("12 µs", 4, None), "");
+    }
+
+    #[test]
+    fn context_time() {
+        assert_eq!(parse_context_time("00:00:01.50"), Some(Duration::new(1, 500_000_000)));
+        assert_eq!(parse_context_time("01:02:03.04"), Some(Duration::new(3723, 40_000_000)));
+        assert_eq!(parse_context_time("00:01:02"), Some(Duration::new(62, 0)));
+        assert_eq!(parse_context_time("01:02"), None);
+        assert_eq!(parse_context_time(""), None);
+        assert_eq!(parse_context_time("aa:bb:cc.dd"), None);
+    }
+
+    #[test]
+    fn context_memory() {
+        assert_eq!(parse_context_memory("0 B"), Some(0));
+        assert_eq!(parse_context_memory("512"), Some(512));
+        assert_eq!(parse_context_memory("12 KB"), Some(12 << 10));
+        assert_eq!(parse_context_memory("3 MB"), Some(3 << 20));
+        assert_eq!(parse_context_memory("2 GB"), Some(2 << 30));
+        assert_eq!(parse_context_memory("KB"), None);
+    }
+
+    #[test]
+    fn context_usage() {
+        assert_eq!(parse_context_usage(CONTEXTS, 1), Some((Duration::new(1, 500_000_000), 12 << 10)));
+        assert_eq!(parse_context_usage(CONTEXTS, 12), Some((Duration::new(3723, 40_000_000), 3 << 20)));
+        assert_eq!(parse_context_usage(CONTEXTS, 345), Some((Duration::new(0, 0), 0)));
+        assert_eq!(parse_context_usage(CONTEXTS, 2), None);
+        assert_eq!(parse_context_usage("", 1), None);
+        assert_eq!(parse_context_usage("PPID  NAME\n0     init\n", 1), None);
+    }
+
+    #[test]
+    fn context_usage_missing_columns() {
+        let without_time = "PID   MEM     NAME\n1     12 KB   init\n";
+        assert_eq!(parse_context_usage(without_time, 1), Some((Duration::new(0, 0), 12 << 10)));
+
+        let without_mem = "PID   TIME        NAME\n1     00:00:01.50 init\n";
+        assert_eq!(parse_context_usage(without_mem, 1), Some((Duration::new(1, 500_000_000), 0)));
+    }
+}
@@ -0,0 +1,37 @@
+use cmp;
+use io::{Error, IoSlice, IoSliceMut, Result};
+use vec::Vec;
+
+use syscall::{read, write};
+
+/// Read into a list of buffers using one read call
+///
+/// Redox has no readv, so the data is read into a single buffer and scattered afterwards.
+pub fn read_vectored(fd: usize, bufs: &mut [IoSliceMut]) -> Result<usize> {
+    let len = bufs.iter().fold(0, |len, buf| len + buf.len());
+    let mut data = vec![0; len];
+    let count = try!(read(fd, &mut data).map_err(|x| Error::from_sys(x)));
+
+    let mut offset = 0;
+    for buf in bufs.iter_mut() {
+        if offset >= count {
+            break;
+        }
+        let n = cmp::min(buf.len(), count - offset);
+        buf[..n].copy_from_slice(&data[offset..offset + n]);
+        offset += n;
+    }
+
+    Ok(count)
+}
+
+/// Write a list of buffers using one write call
+///
+/// Redox has no writev, so the buffers are gathered into a single buffer first.
+pub fn write_vectored(fd: usize, bufs: &[IoSlice]) -> Result<usize> {
+    let mut data = Vec::new();
+    for buf in bufs.iter() {
+        data.extend_from_slice(buf);
+    }
+    write(fd, &data).map_err(|x| Error::from_sys(x))
+}
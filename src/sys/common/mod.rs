@@ -1,3 +1,4 @@
+pub mod io;
 pub mod net;
 
 /// A trait for viewing representations from std types
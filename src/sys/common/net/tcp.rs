@@ -1,6 +1,6 @@
 use cell::UnsafeCell;
 use fs::File;
-use io::{Error, ErrorKind, Result, Read, Write};
+use io::{Error, ErrorKind, IoSlice, IoSliceMut, Result, Read, Write};
 use net::{SocketAddr, Shutdown};
 use time::Duration;
 use vec::Vec;
@@ -32,10 +32,18 @@ impl TcpStream {
         self.get().read_to_end(buf)
     }
 
+    pub fn read_vectored(&self, bufs: &mut [IoSliceMut]) -> Result<usize> {
+        self.get().read_vectored(bufs)
+    }
+
     pub fn write(&self, buf: &[u8]) -> Result<usize> {
         self.get().write(buf)
     }
 
+    pub fn write_vectored(&self, bufs: &[IoSlice]) -> Result<usize> {
+        self.get().write_vectored(bufs)
+    }
+
     pub fn take_error(&self) -> Result<Option<Error>> {
         Ok(None)
     }